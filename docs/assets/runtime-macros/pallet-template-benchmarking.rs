@@ -0,0 +1,199 @@
+//! Benchmarking setup for pallet-template
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_system::{RawOrigin, offchain::{AppCrypto, SignedPayload}};
+use frame_support::traits::{Currency, OnInitialize, ReservableCurrency};
+use frame_benchmarking::{benchmarks, account};
+use sp_runtime::{RuntimeAppPublic, traits::{Bounded, Zero}};
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+// Fills the history of `who` up to `T::HistoryDepth`, so the next write has to evict
+// the oldest entry. This is the most expensive path through `Module::store`.
+fn fill_history<T: Trait>(who: &T::AccountId) {
+	for value in 0..=T::HistoryDepth::get() {
+		Module::<T>::store(who, T::Value::from(value));
+	}
+}
+
+// Gives `who` enough funds to pay the value deposit.
+fn fund<T: Trait>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value());
+}
+
+// Funds `who` and reserves its value deposit, so clearing its value has to return it.
+fn hold_deposit<T: Trait>(who: &T::AccountId) -> Result<(), &'static str> {
+	fund::<T>(who);
+	Module::<T>::reserve_deposit(who)?;
+	Ok(())
+}
+
+benchmarks! {
+	_ { }
+
+	do_something {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		fill_history::<T>(&caller);
+		fund::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), T::Value::from(42u32))
+	verify {
+		assert_eq!(Something::<T>::get(&caller), Some(T::Value::from(42u32)));
+	}
+
+	cause_error {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		fill_history::<T>(&caller);
+		let current = T::Value::from(T::HistoryDepth::get());
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Something::<T>::get(&caller), Some(current + T::Step::get()));
+	}
+
+	increment_by {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		fill_history::<T>(&caller);
+		let current = T::Value::from(T::HistoryDepth::get());
+	}: _(RawOrigin::Signed(caller.clone()), T::Value::from(1u32))
+	verify {
+		assert_eq!(Something::<T>::get(&caller), Some(current + T::Value::from(1u32)));
+	}
+
+	decrement_by {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		fill_history::<T>(&caller);
+		let current = T::Value::from(T::HistoryDepth::get());
+	}: _(RawOrigin::Signed(caller.clone()), current)
+	verify {
+		assert_eq!(Something::<T>::get(&caller), Some(Zero::zero()));
+	}
+
+	saturating_increment_by {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		fill_history::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), T::Value::max_value())
+	verify {
+		assert_eq!(Something::<T>::get(&caller), Some(T::Value::max_value()));
+	}
+
+	saturating_decrement_by {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		fill_history::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), T::Value::max_value())
+	verify {
+		assert_eq!(Something::<T>::get(&caller), Some(Zero::zero()));
+	}
+
+	clear_something {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		fill_history::<T>(&caller);
+		hold_deposit::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Something::<T>::get(&caller), None);
+		assert!(SomethingHistory::<T>::get(&caller).is_empty());
+		assert!(T::Currency::reserved_balance(&caller).is_zero());
+	}
+
+	on_initialize {
+		let n in 0 .. 100;
+		for i in 0 .. n {
+			let who: T::AccountId = account("who", i, SEED);
+			Module::<T>::store(&who, T::Value::from(i));
+			// Written directly, as `fill_history` would schedule an expiry for every write.
+			let history = vec![T::Value::from(i); T::HistoryDepth::get() as usize];
+			SomethingHistory::<T>::insert(&who, history);
+			hold_deposit::<T>(&who)?;
+		}
+		let expires_at = frame_system::Module::<T>::block_number() + T::ValueLifetime::get();
+	}: { Module::<T>::on_initialize(expires_at); }
+	verify {
+		assert!(Expiries::<T>::get(expires_at).is_empty());
+		if n > 0 {
+			let who: T::AccountId = account("who", 0, SEED);
+			assert!(SomethingHistory::<T>::get(&who).is_empty());
+		}
+	}
+
+	force_set {
+		let who: T::AccountId = account("who", 0, SEED);
+		fill_history::<T>(&who);
+		let origin = T::AdminOrigin::successful_origin();
+	}: { Module::<T>::force_set(origin, who.clone(), T::Value::from(42u32))?; }
+	verify {
+		assert_eq!(Something::<T>::get(&who), Some(T::Value::from(42u32)));
+	}
+
+	force_clear {
+		let who: T::AccountId = account("who", 0, SEED);
+		fill_history::<T>(&who);
+		hold_deposit::<T>(&who)?;
+		let origin = T::AdminOrigin::successful_origin();
+	}: { Module::<T>::force_clear(origin, who.clone())?; }
+	verify {
+		assert_eq!(Something::<T>::get(&who), None);
+	}
+
+	set_paused {
+		let origin = T::AdminOrigin::successful_origin();
+	}: { Module::<T>::set_paused(origin, true)?; }
+	verify {
+		assert!(<Paused>::get());
+	}
+
+	set_authorities {
+		let a in 0 .. 100;
+		let authorities: Vec<T::AccountId> = (0..a).map(|i| account("authority", i, SEED)).collect();
+		let origin = T::AdminOrigin::successful_origin();
+	}: { Module::<T>::set_authorities(origin, authorities)?; }
+	verify {
+		assert_eq!(Authorities::<T>::get().len(), a as usize);
+	}
+
+	submit_something_unsigned {
+		let public = <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::generate_pair(None);
+		let public: T::Public = <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic::from(public).into();
+		let who = public.clone().into_account();
+		fill_history::<T>(&who);
+		Authorities::<T>::put(vec![who.clone()]);
+		let payload = SomethingPayload {
+			block_number: frame_system::Module::<T>::block_number(),
+			value: T::Value::from(42u32),
+			public,
+		};
+		let signature = SignedPayload::<T>::sign::<T::AuthorityId>(&payload)
+			.ok_or("Unable to sign the payload")?;
+	}: _(RawOrigin::None, payload, signature)
+	verify {
+		assert_eq!(Something::<T>::get(&who), Some(T::Value::from(42u32)));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_do_something::<Test>());
+			assert_ok!(test_benchmark_cause_error::<Test>());
+			assert_ok!(test_benchmark_increment_by::<Test>());
+			assert_ok!(test_benchmark_decrement_by::<Test>());
+			assert_ok!(test_benchmark_saturating_increment_by::<Test>());
+			assert_ok!(test_benchmark_saturating_decrement_by::<Test>());
+			assert_ok!(test_benchmark_clear_something::<Test>());
+			assert_ok!(test_benchmark_on_initialize::<Test>());
+			assert_ok!(test_benchmark_force_set::<Test>());
+			assert_ok!(test_benchmark_force_clear::<Test>());
+			assert_ok!(test_benchmark_set_paused::<Test>());
+			assert_ok!(test_benchmark_set_authorities::<Test>());
+			assert_ok!(test_benchmark_submit_something_unsigned::<Test>());
+		});
+	}
+}
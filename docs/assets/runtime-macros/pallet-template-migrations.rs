@@ -0,0 +1,64 @@
+//! Storage migrations for the template pallet.
+
+use super::*;
+use frame_support::storage::migration::{get_storage_value, take_storage_value};
+
+/// The name of the single value shared by all accounts in version 1.
+///
+/// It is stored under the prefix of the instance, which is the same in both versions.
+const LEGACY_ITEM: &[u8] = b"Something";
+
+/// Moves the single value of version 1 to `T::LegacyValueOwner`.
+///
+/// The value becomes the owner's current value, recorded as written at the upgrade block,
+/// unless the owner already has a value of its own, in which case the legacy value is
+/// dropped. Running it again once `StorageVersion` is `V2` does nothing.
+pub fn migrate_to_v2<T: Trait<I>, I: Instance>() -> Weight {
+	// Reading `StorageVersion`.
+	let mut weight = T::DbWeight::get().reads(1);
+	if <StorageVersion<I>>::get() != Releases::V1 {
+		return weight;
+	}
+
+	// Taking the legacy value and writing `StorageVersion`.
+	weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 2));
+	if let Some(value) = take_storage_value::<u32>(I::PREFIX.as_bytes(), LEGACY_ITEM, &[]) {
+		let owner = T::LegacyValueOwner::get();
+
+		// Checking whether the owner has a value.
+		weight = weight.saturating_add(T::DbWeight::get().reads(1));
+		if !<Something<T, I>>::contains_key(&owner) {
+			Module::<T, I>::store(&owner, T::Value::from(value));
+			// `store` costs the same as `force_set` without its origin check and event.
+			weight = weight.saturating_add(T::WeightInfo::force_set());
+		}
+	}
+
+	<StorageVersion<I>>::put(Releases::V2);
+	weight
+}
+
+/// Checks that the storage is ready for `migrate_to_v2`.
+///
+/// Returns the legacy value, which is to be passed to `post_migrate_to_v2`.
+pub fn pre_migrate_to_v2<T: Trait<I>, I: Instance>() -> Result<Option<u32>, &'static str> {
+	if <StorageVersion<I>>::get() != Releases::V1 {
+		return Err("Storage is not at version 1");
+	}
+	Ok(get_storage_value::<u32>(I::PREFIX.as_bytes(), LEGACY_ITEM, &[]))
+}
+
+/// Checks that `migrate_to_v2` left the storage as expected, given the legacy value
+/// returned by `pre_migrate_to_v2`.
+pub fn post_migrate_to_v2<T: Trait<I>, I: Instance>(legacy: Option<u32>) -> Result<(), &'static str> {
+	if <StorageVersion<I>>::get() != Releases::V2 {
+		return Err("Storage is not at version 2");
+	}
+	if get_storage_value::<u32>(I::PREFIX.as_bytes(), LEGACY_ITEM, &[]).is_some() {
+		return Err("The legacy value was not removed");
+	}
+	if legacy.is_some() && !<Something<T, I>>::contains_key(T::LegacyValueOwner::get()) {
+		return Err("The legacy owner has no value");
+	}
+	Ok(())
+}
@@ -1,12 +1,18 @@
 // Creating mock runtime here
 
-use crate::{Module, Trait};
+use crate::{Module, Trait, Instance1};
 use sp_core::H256;
-use frame_support::{impl_outer_origin, impl_outer_event, parameter_types, weights::Weight};
+use frame_support::{
+	impl_outer_origin, impl_outer_event, impl_outer_dispatch, parameter_types,
+	traits::{Get, OnInitialize, OnFinalize},
+	weights::{Weight, RuntimeDbWeight},
+};
+use std::cell::RefCell;
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
+	traits::{BlakeTwo256, IdentityLookup, Extrinsic as ExtrinsicT},
+	testing::{Header, TestXt, TestSignature, UintAuthorityId}, Perbill,
 };
-use frame_system as system;
+use frame_system::{self as system, offchain::AppCrypto};
 use crate as pallet_template;
 
 impl_outer_origin! {
@@ -16,7 +22,17 @@ impl_outer_origin! {
 impl_outer_event! {
 	pub enum TestEvent for Test {
 		system<T>,
+		pallet_balances<T>,
 		pallet_template<T>,
+		pallet_template Instance1<T>,
+	}
+}
+
+// Offchain workers of both instances submit their calls through this type.
+impl_outer_dispatch! {
+	pub enum OuterCall for Test where origin: Origin {
+		pallet_template::TemplateModule,
+		pallet_template::SecondTemplateModule,
 	}
 }
 
@@ -30,8 +46,17 @@ parameter_types! {
 	pub const MaximumBlockWeight: Weight = 1024;
 	pub const MaximumBlockLength: u32 = 2 * 1024;
 	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+	pub const DbWeight: RuntimeDbWeight = RuntimeDbWeight { read: 10, write: 100 };
+	pub const HistoryDepth: u32 = 3;
+	pub const UnsignedPriority: u64 = 100;
+	pub const UnsignedInterval: u64 = 3;
+	pub const LegacyValueOwner: u64 = 100;
+	pub const Step: u32 = 1;
+	pub const SecondStep: u32 = 5;
+	pub const ValueDeposit: u64 = 10;
 }
 impl system::Trait for Test {
+	type BaseCallFilter = ();
 	type Origin = Origin;
 	type Call = ();
 	type Index = u64;
@@ -44,7 +69,7 @@ impl system::Trait for Test {
 	type Event = TestEvent;
 	type BlockHashCount = BlockHashCount;
 	type MaximumBlockWeight = MaximumBlockWeight;
-	type DbWeight = ();
+	type DbWeight = DbWeight;
 	type BlockExecutionWeight = ();
 	type ExtrinsicBaseWeight = ();
 	type MaximumExtrinsicWeight = MaximumBlockWeight;
@@ -52,17 +77,248 @@ impl system::Trait for Test {
 	type AvailableBlockRatio = AvailableBlockRatio;
 	type Version = ();
 	type ModuleToIndex = ();
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 }
+
+thread_local! {
+	static EXISTENTIAL_DEPOSIT: RefCell<u64> = RefCell::new(1);
+	static VALUE_LIFETIME: RefCell<u64> = RefCell::new(5);
+}
+
+// Set by `ExtBuilder::existential_deposit`.
+pub struct ExistentialDeposit;
+impl Get<u64> for ExistentialDeposit {
+	fn get() -> u64 {
+		EXISTENTIAL_DEPOSIT.with(|v| *v.borrow())
+	}
+}
+
+// 5 blocks in every test, unless changed with `set_value_lifetime`.
+pub struct ValueLifetime;
+impl Get<u64> for ValueLifetime {
+	fn get() -> u64 {
+		VALUE_LIFETIME.with(|v| *v.borrow())
+	}
+}
+
+// Changes `ValueLifetime` in the middle of a test, as a runtime upgrade would.
+pub fn set_value_lifetime(lifetime: u64) {
+	VALUE_LIFETIME.with(|v| *v.borrow_mut() = lifetime);
+}
+
+impl pallet_balances::Trait for Test {
+	type Balance = u64;
+	type Event = TestEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+}
+
+pub type Extrinsic = TestXt<OuterCall, ()>;
+
+impl system::offchain::SigningTypes for Test {
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
+}
+
+impl<LocalCall> system::offchain::SendTransactionTypes<LocalCall> for Test where
+	OuterCall: From<LocalCall>,
+{
+	type OverarchingCall = OuterCall;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> system::offchain::CreateSignedTransaction<LocalCall> for Test where
+	OuterCall: From<LocalCall>,
+{
+	fn create_transaction<C: AppCrypto<Self::Public, Self::Signature>>(
+		call: OuterCall,
+		_public: UintAuthorityId,
+		account: u64,
+		_nonce: u64,
+	) -> Option<(OuterCall, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (account, ())))
+	}
+}
+
+// Signs with `UintAuthorityId`, whose keys are set with `UintAuthorityId::set_all_keys`.
+pub struct TestAuthId;
+impl AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+	type RuntimeAppPublic = UintAuthorityId;
+	type GenericPublic = UintAuthorityId;
+	type GenericSignature = TestSignature;
+}
+
 impl Trait for Test {
+	type AuthorityId = TestAuthId;
 	type Event = TestEvent;
+	type Value = u32;
+	type Step = Step;
+	type Currency = Balances;
+	type ValueDeposit = ValueDeposit;
+	type HistoryDepth = HistoryDepth;
+	type ValueLifetime = ValueLifetime;
+	type AdminOrigin = system::EnsureRoot<u64>;
+	type UnsignedPriority = UnsignedPriority;
+	type UnsignedInterval = UnsignedInterval;
+	type LegacyValueOwner = LegacyValueOwner;
+	type WeightInfo = ();
 }
+// A second instance with a different step, to check that instances are isolated.
+impl Trait<Instance1> for Test {
+	type AuthorityId = TestAuthId;
+	type Event = TestEvent;
+	type Value = u32;
+	type Step = SecondStep;
+	type Currency = Balances;
+	type ValueDeposit = ValueDeposit;
+	type HistoryDepth = HistoryDepth;
+	type ValueLifetime = ValueLifetime;
+	type AdminOrigin = system::EnsureRoot<u64>;
+	type UnsignedPriority = UnsignedPriority;
+	type UnsignedInterval = UnsignedInterval;
+	type LegacyValueOwner = LegacyValueOwner;
+	type WeightInfo = ();
+}
+pub type System = system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
 pub type TemplateModule = Module<Test>;
+pub type SecondTemplateModule = Module<Test, Instance1>;
+
+// The accounts `ExtBuilder` endows with `ENDOWMENT` by default. Any other account has no funds.
+pub const ENDOWED: [u64; 5] = [1, 2, 3, 7, 100];
+pub const ENDOWMENT: u64 = 100;
+
+// Builds the test externalities with the given balances, template genesis and
+// existential deposit.
+pub struct ExtBuilder {
+	existential_deposit: u64,
+	balances: Vec<(u64, u64)>,
+	initial_values: Vec<(u64, u32)>,
+	authorities: Vec<u64>,
+	second_instance: Option<pallet_template::GenesisConfig<Test, Instance1>>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			existential_deposit: 1,
+			balances: ENDOWED.iter().map(|&who| (who, ENDOWMENT)).collect(),
+			initial_values: Vec::new(),
+			// The key the unsigned payloads in the tests are signed with.
+			authorities: vec![7],
+			second_instance: None,
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn existential_deposit(mut self, existential_deposit: u64) -> Self {
+		self.existential_deposit = existential_deposit;
+		self
+	}
+
+	// Replaces the default endowed accounts.
+	pub fn balances(mut self, balances: Vec<(u64, u64)>) -> Self {
+		self.balances = balances;
+		self
+	}
+
+	// Sets the `initial_values` of the default template instance's genesis.
+	pub fn initial_values(mut self, initial_values: Vec<(u64, u32)>) -> Self {
+		self.initial_values = initial_values;
+		self
+	}
+
+	// Sets the `authorities` of the default template instance's genesis.
+	pub fn authorities(mut self, authorities: Vec<u64>) -> Self {
+		self.authorities = authorities;
+		self
+	}
+
+	// Sets the genesis of the second template instance, which is empty otherwise.
+	pub fn second_instance(mut self, initial_values: Vec<(u64, u32)>, authorities: Vec<u64>) -> Self {
+		self.second_instance = Some(pallet_template::GenesisConfig { initial_values, authorities });
+		self
+	}
+
+	fn build_storage(&self) -> sp_runtime::Storage {
+		EXISTENTIAL_DEPOSIT.with(|v| *v.borrow_mut() = self.existential_deposit);
+		set_value_lifetime(5);
+		let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+		pallet_balances::GenesisConfig::<Test> {
+			balances: self.balances.clone(),
+		}.assimilate_storage(&mut t).unwrap();
+		t
+	}
+
+	// Builds the externalities at block 1, as events are not recorded in the genesis block.
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = self.build_storage();
+		pallet_template::GenesisConfig::<Test> {
+			initial_values: self.initial_values,
+			authorities: self.authorities,
+		}.assimilate_storage(&mut t).unwrap();
+		if let Some(config) = self.second_instance {
+			config.assimilate_storage(&mut t).unwrap();
+		}
+		let mut ext: sp_io::TestExternalities = t.into();
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	// Builds the storage of a chain that ran version 1 of the pallet, where `Something` was
+	// a single value shared by all accounts. The template genesis is not applied.
+	pub fn build_v1(self, something: Option<u32>) -> sp_io::TestExternalities {
+		let mut ext: sp_io::TestExternalities = self.build_storage().into();
+		ext.execute_with(|| {
+			System::set_block_number(1);
+			if let Some(value) = something {
+				frame_support::storage::migration::put_storage_value(
+					b"TemplateModule", b"Something", &[], value,
+				);
+			}
+		});
+		ext
+	}
+}
 
 // This function basically just builds a genesis storage key/value store according to
 // our desired mockup.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	ExtBuilder::default().build()
+}
+
+// Moves to block `n`, running the hooks of every pallet in the mock on the way.
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		SecondTemplateModule::on_finalize(System::block_number());
+		TemplateModule::on_finalize(System::block_number());
+		System::on_finalize(System::block_number());
+		System::set_block_number(System::block_number() + 1);
+		System::on_initialize(System::block_number());
+		TemplateModule::on_initialize(System::block_number());
+		SecondTemplateModule::on_initialize(System::block_number());
+	}
+}
+
+// The events recorded by `System`, oldest first.
+pub fn events() -> Vec<TestEvent> {
+	System::events().into_iter().map(|record| record.event).collect()
+}
+
+// The most recent event recorded by `System`.
+pub fn last_event() -> TestEvent {
+	System::events().pop().expect("An event was expected").event
+}
+
+// Asserts that `System` recorded exactly the given events, in order.
+#[macro_export]
+macro_rules! assert_events {
+	($($event:expr),* $(,)?) => {{
+		let expected: Vec<$crate::mock::TestEvent> = vec![$($event.into()),*];
+		assert_eq!($crate::mock::events(), expected);
+	}};
 }
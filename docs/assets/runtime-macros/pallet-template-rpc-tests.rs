@@ -0,0 +1,112 @@
+use super::*;
+use jsonrpc_core::IoHandler;
+use sp_api::ApiRef;
+use sp_blockchain::{BlockStatus, Info};
+use substrate_test_runtime_client::runtime::{Block, Hash, Header};
+
+#[derive(Clone)]
+struct TestApi;
+
+sp_api::mock_impl_runtime_apis! {
+	impl TemplateRuntimeApi<Block, u64, u32> for TestApi {
+		fn get_something(account: u64) -> Option<u32> {
+			if account == 1 { Some(42) } else { None }
+		}
+
+		fn get_something_history(account: u64) -> Vec<u32> {
+			if account == 1 { vec![40, 41] } else { Vec::new() }
+		}
+
+		fn get_statistics() -> SomethingStatistics<u32> {
+			SomethingStatistics { accounts: 1, total: 42, min: Some(42), max: Some(42) }
+		}
+	}
+}
+
+/// A client with a single, empty best block, serving `TestApi`.
+struct TestClient;
+
+impl ProvideRuntimeApi<Block> for TestClient {
+	type Api = TestApi;
+
+	fn runtime_api<'a>(&'a self) -> ApiRef<'a, Self::Api> {
+		TestApi.into()
+	}
+}
+
+impl HeaderBackend<Block> for TestClient {
+	fn header(&self, _id: BlockId<Block>) -> sp_blockchain::Result<Option<Header>> {
+		Ok(None)
+	}
+
+	fn info(&self) -> Info<Block> {
+		Info {
+			best_hash: Default::default(),
+			best_number: 0,
+			genesis_hash: Default::default(),
+			finalized_hash: Default::default(),
+			finalized_number: 0,
+			number_leaves: 0,
+		}
+	}
+
+	fn status(&self, _id: BlockId<Block>) -> sp_blockchain::Result<BlockStatus> {
+		Ok(BlockStatus::Unknown)
+	}
+
+	fn number(&self, _hash: Hash) -> sp_blockchain::Result<Option<u64>> {
+		Ok(None)
+	}
+
+	fn hash(&self, _number: u64) -> sp_blockchain::Result<Option<Hash>> {
+		Ok(None)
+	}
+}
+
+fn template() -> Template<TestClient, Block> {
+	Template::new(Arc::new(TestClient), DenyUnsafe::No)
+}
+
+fn io(deny_unsafe: DenyUnsafe) -> IoHandler {
+	let mut io = IoHandler::new();
+	let template = Template::new(Arc::new(TestClient), deny_unsafe);
+	io.extend_with(TemplateRpc::<Hash, u64, u32>::to_delegate(template));
+	io
+}
+
+#[test]
+fn get_something_calls_runtime_api() {
+	assert_eq!(template().get_something(1, None).unwrap(), Some(42));
+	assert_eq!(template().get_something(2, Some(Default::default())).unwrap(), None);
+}
+
+#[test]
+fn get_something_history_calls_runtime_api() {
+	assert_eq!(template().get_something_history(1, None).unwrap(), vec![40, 41]);
+	assert!(template().get_something_history(2, None).unwrap().is_empty());
+}
+
+#[test]
+fn requests_are_served_over_json_rpc() {
+	let request = r#"{"jsonrpc":"2.0","method":"template_getSomething","params":[1],"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":42,"id":1}"#;
+	assert_eq!(io(DenyUnsafe::No).handle_request_sync(request), Some(response.into()));
+
+	let request = r#"{"jsonrpc":"2.0","method":"template_getStatistics","params":[],"id":2}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"accounts":1,"max":42,"min":42,"total":42},"id":2}"#;
+	assert_eq!(io(DenyUnsafe::No).handle_request_sync(request), Some(response.into()));
+}
+
+#[test]
+fn statistics_are_denied_when_unsafe_calls_are() {
+	let io = io(DenyUnsafe::Yes);
+
+	let request = r#"{"jsonrpc":"2.0","method":"template_getSomething","params":[1],"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":42,"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.into()));
+
+	let request = r#"{"jsonrpc":"2.0","method":"template_getStatistics","params":[],"id":2}"#;
+	let response = io.handle_request_sync(request).unwrap();
+	assert!(response.contains(r#""error""#));
+	assert!(!response.contains(r#""result""#));
+}
@@ -0,0 +1,115 @@
+//! RPC interface for the template pallet.
+
+use std::sync::Arc;
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sc_rpc_api::DenyUnsafe;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+pub use template_runtime_api::TemplateApi as TemplateRuntimeApi;
+use template_runtime_api::SomethingStatistics;
+
+#[cfg(test)]
+mod tests;
+
+#[rpc]
+pub trait TemplateRpc<BlockHash, AccountId, Value> {
+	/// The value stored for `account` at the given block, or the best block.
+	#[rpc(name = "template_getSomething")]
+	fn get_something(&self, account: AccountId, at: Option<BlockHash>) -> Result<Option<Value>>;
+
+	/// The previous values of `account` at the given block, or the best block.
+	#[rpc(name = "template_getSomethingHistory")]
+	fn get_something_history(&self, account: AccountId, at: Option<BlockHash>) -> Result<Vec<Value>>;
+
+	/// Aggregate statistics over all stored values at the given block, or the best block.
+	///
+	/// This reads every stored value, so it is an unsafe method: it is only served when the
+	/// node allows unsafe RPC calls, for example with `--rpc-methods=Unsafe`.
+	#[rpc(name = "template_getStatistics")]
+	fn get_statistics(&self, at: Option<BlockHash>) -> Result<SomethingStatistics<Value>>;
+}
+
+/// A struct that implements the [`TemplateRpc`].
+pub struct Template<C, B> {
+	client: Arc<C>,
+	deny_unsafe: DenyUnsafe,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Template<C, B> {
+	/// Create new `Template` with the given reference to the client. `deny_unsafe` decides
+	/// whether the unsafe methods are served.
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
+		Template { client, deny_unsafe, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::RuntimeError.into()),
+		message: "Unable to query the template pallet.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+impl<C, Block> Template<C, Block> where
+	Block: BlockT,
+	C: HeaderBackend<Block>,
+{
+	fn block_id(&self, at: Option<<Block as BlockT>::Hash>) -> BlockId<Block> {
+		// If the block hash is not supplied assume the best block.
+		BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash))
+	}
+}
+
+impl<C, Block, AccountId, Value> TemplateRpc<<Block as BlockT>::Hash, AccountId, Value>
+	for Template<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: TemplateRuntimeApi<Block, AccountId, Value>,
+	AccountId: Codec,
+	Value: Codec,
+{
+	fn get_something(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Value>> {
+		let at = self.block_id(at);
+		self.client.runtime_api().get_something(&at, account).map_err(runtime_error)
+	}
+
+	fn get_something_history(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<Value>> {
+		let at = self.block_id(at);
+		self.client.runtime_api().get_something_history(&at, account).map_err(runtime_error)
+	}
+
+	fn get_statistics(&self, at: Option<<Block as BlockT>::Hash>) -> Result<SomethingStatistics<Value>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let at = self.block_id(at);
+		self.client.runtime_api().get_statistics(&at).map_err(runtime_error)
+	}
+}
@@ -0,0 +1,25 @@
+//! Runtime API definition for the template pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+pub use template::SomethingStatistics;
+
+sp_api::decl_runtime_apis! {
+	/// The API to read the template pallet's state.
+	pub trait TemplateApi<AccountId, Value> where
+		AccountId: Codec,
+		Value: Codec,
+	{
+		/// The value stored for `account`, if any.
+		fn get_something(account: AccountId) -> Option<Value>;
+		/// The previous values of `account`, oldest first.
+		fn get_something_history(account: AccountId) -> Vec<Value>;
+		/// Aggregate statistics over all stored values.
+		///
+		/// This reads every stored value, so its cost grows with the number of accounts.
+		fn get_statistics() -> SomethingStatistics<Value>;
+	}
+}
@@ -0,0 +1,789 @@
+// Tests to be written here
+
+use crate::{
+	DefaultInstance, Error, RawEvent, Releases, StorageVersion, migrations, SomethingPayload, SomethingStatistics, WeightInfo, OFFCHAIN_VALUE_KEY, OFFCHAIN_UNSIGNED_VALUE_KEY,
+	assert_events, mock::*,
+};
+use codec::{Encode, Decode};
+use frame_support::{
+	assert_ok, assert_noop,
+	traits::{Get, OnInitialize, OffchainWorker, OnRuntimeUpgrade},
+	unsigned::ValidateUnsigned,
+};
+use sp_core::offchain::{
+	OffchainExt, TransactionPoolExt,
+	testing::{TestOffchainExt, TestTransactionPoolExt},
+};
+use sp_runtime::{
+	DispatchError, RuntimeAppPublic,
+	offchain::storage::StorageValueRef,
+	testing::{TestSignature, UintAuthorityId},
+	transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidity},
+};
+
+fn signed_payload(
+	signer: u64,
+	block_number: u64,
+	value: u32,
+) -> (SomethingPayload<UintAuthorityId, u64, u32>, TestSignature) {
+	let public = UintAuthorityId(signer);
+	let payload = SomethingPayload { block_number, value, public: public.clone() };
+	let signature = public.sign(&payload.encode()).unwrap();
+	(payload, signature)
+}
+
+fn validate(payload: SomethingPayload<UintAuthorityId, u64, u32>, signature: TestSignature) -> TransactionValidity {
+	TemplateModule::validate_unsigned(
+		TransactionSource::External,
+		&crate::Call::submit_something_unsigned(payload, signature),
+	)
+}
+
+#[test]
+fn it_works_for_default_value() {
+	new_test_ext().execute_with(|| {
+		// Dispatch a signed extrinsic.
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		// Read pallet storage and assert an expected result.
+		assert_eq!(TemplateModule::something(1), Some(42));
+		// Check that the expected event was deposited.
+		assert_eq!(last_event(), TestEvent::pallet_template(RawEvent::SomethingStored(42, 1)));
+	});
+}
+
+#[test]
+fn correct_error_for_storage_overflow() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), u32::max_value()));
+
+		// Ensure the expected error is thrown when the value cannot be incremented.
+		assert_noop!(
+			TemplateModule::cause_error(Origin::signed(1)),
+			Error::<Test>::StorageOverflow
+		);
+		assert_eq!(TemplateModule::something(1), Some(u32::max_value()));
+	});
+}
+
+#[test]
+fn cause_error_deposits_no_event() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::force_set(Origin::root(), 1, 41));
+		assert_ok!(TemplateModule::cause_error(Origin::signed(1)));
+
+		assert_eq!(TemplateModule::something(1), Some(42));
+		assert_events!(TestEvent::pallet_template(RawEvent::SomethingStored(41, 1)));
+	});
+}
+
+#[test]
+fn arithmetic_calls_deposit_the_new_value() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::force_set(Origin::root(), 1, 10));
+		assert_ok!(TemplateModule::increment_by(Origin::signed(1), 5));
+		assert_ok!(TemplateModule::decrement_by(Origin::signed(1), 3));
+		assert_ok!(TemplateModule::saturating_increment_by(Origin::signed(1), u32::max_value()));
+		assert_ok!(TemplateModule::saturating_decrement_by(Origin::signed(1), u32::max_value()));
+
+		assert_events!(
+			TestEvent::pallet_template(RawEvent::SomethingStored(10, 1)),
+			TestEvent::pallet_template(RawEvent::SomethingStored(15, 1)),
+			TestEvent::pallet_template(RawEvent::SomethingStored(12, 1)),
+			TestEvent::pallet_template(RawEvent::SomethingStored(u32::max_value(), 1)),
+			TestEvent::pallet_template(RawEvent::SomethingStored(0, 1)),
+		);
+	});
+}
+
+#[test]
+fn clear_something_deposits_something_cleared() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::clear_something(Origin::signed(1)));
+
+		assert_eq!(last_event(), TestEvent::pallet_template(RawEvent::SomethingCleared(1)));
+	});
+}
+
+#[test]
+fn set_paused_deposits_paused_and_unpaused() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set_paused(Origin::root(), true));
+		assert_ok!(TemplateModule::set_paused(Origin::root(), false));
+
+		assert_events!(
+			TestEvent::pallet_template(RawEvent::Paused),
+			TestEvent::pallet_template(RawEvent::Unpaused),
+		);
+	});
+}
+
+#[test]
+fn failed_calls_deposit_no_events() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+		assert_noop!(TemplateModule::clear_something(Origin::signed(1)), Error::<Test>::NoneValue);
+		assert_noop!(TemplateModule::set_paused(Origin::signed(1), true), DispatchError::BadOrigin);
+
+		assert!(System::events().is_empty());
+	});
+}
+
+#[test]
+fn correct_error_for_none_value() {
+	new_test_ext().execute_with(|| {
+		// Ensure the expected error is thrown when no value is present.
+		assert_noop!(
+			TemplateModule::cause_error(Origin::signed(1)),
+			Error::<Test>::NoneValue
+		);
+	});
+}
+
+#[test]
+fn values_are_stored_per_account() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::do_something(Origin::signed(2), 7));
+		assert_ok!(TemplateModule::cause_error(Origin::signed(2)));
+
+		assert_eq!(TemplateModule::something(1), Some(42));
+		assert_eq!(TemplateModule::something(2), Some(8));
+		assert_eq!(TemplateModule::something(3), None);
+	});
+}
+
+#[test]
+fn cause_error_adds_the_configured_step() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 10));
+		assert_ok!(SecondTemplateModule::do_something(Origin::signed(1), 10));
+
+		assert_ok!(TemplateModule::cause_error(Origin::signed(1)));
+		assert_ok!(SecondTemplateModule::cause_error(Origin::signed(1)));
+
+		// `Step` is 1 for the first instance and 5 for the second.
+		assert_eq!(TemplateModule::something(1), Some(11));
+		assert_eq!(SecondTemplateModule::something(1), Some(15));
+	});
+}
+
+#[test]
+fn checked_arithmetic_fails_on_overflow_and_underflow() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(TemplateModule::increment_by(Origin::signed(1), 1), Error::<Test>::NoneValue);
+		assert_noop!(TemplateModule::decrement_by(Origin::signed(1), 1), Error::<Test>::NoneValue);
+
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 10));
+		assert_ok!(TemplateModule::increment_by(Origin::signed(1), 5));
+		assert_eq!(TemplateModule::something(1), Some(15));
+		assert_ok!(TemplateModule::decrement_by(Origin::signed(1), 15));
+		assert_eq!(TemplateModule::something(1), Some(0));
+
+		assert_noop!(
+			TemplateModule::decrement_by(Origin::signed(1), 1),
+			Error::<Test>::StorageUnderflow
+		);
+		assert_ok!(TemplateModule::increment_by(Origin::signed(1), u32::max_value()));
+		assert_noop!(
+			TemplateModule::increment_by(Origin::signed(1), 1),
+			Error::<Test>::StorageOverflow
+		);
+		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::StorageOverflow);
+	});
+}
+
+#[test]
+fn saturating_arithmetic_stops_at_the_bounds() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::saturating_increment_by(Origin::signed(1), 1),
+			Error::<Test>::NoneValue
+		);
+
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 10));
+		assert_ok!(TemplateModule::saturating_decrement_by(Origin::signed(1), 11));
+		assert_eq!(TemplateModule::something(1), Some(0));
+
+		assert_ok!(TemplateModule::saturating_increment_by(Origin::signed(1), u32::max_value()));
+		assert_ok!(TemplateModule::saturating_increment_by(Origin::signed(1), 1));
+		assert_eq!(TemplateModule::something(1), Some(u32::max_value()));
+		assert_eq!(TemplateModule::something_history(1), vec![10, 0, u32::max_value()]);
+	});
+}
+
+#[test]
+fn do_something_reserves_a_deposit_once() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_eq!(Balances::reserved_balance(1), ValueDeposit::get());
+		assert_eq!(TemplateModule::deposit_of(1), ValueDeposit::get());
+
+		// Rewriting or changing the value does not reserve again.
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 43));
+		assert_ok!(TemplateModule::increment_by(Origin::signed(1), 1));
+		assert_eq!(Balances::reserved_balance(1), ValueDeposit::get());
+		assert_eq!(Balances::free_balance(1), ENDOWMENT - ValueDeposit::get());
+	});
+}
+
+#[test]
+fn do_something_fails_without_funds_for_the_deposit() {
+	new_test_ext().execute_with(|| {
+		// Account 4 is not endowed in the mock.
+		assert_noop!(
+			TemplateModule::do_something(Origin::signed(4), 42),
+			Error::<Test>::InsufficientDeposit
+		);
+		assert_eq!(TemplateModule::something(4), None);
+	});
+}
+
+#[test]
+fn deposit_is_limited_by_the_free_balance() {
+	ExtBuilder::default()
+		.balances(vec![(1, ValueDeposit::get() - 1), (2, ValueDeposit::get())])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				TemplateModule::do_something(Origin::signed(1), 42),
+				Error::<Test>::InsufficientDeposit
+			);
+			assert_ok!(TemplateModule::do_something(Origin::signed(2), 42));
+			assert_eq!(Balances::free_balance(2), 0);
+		});
+}
+
+#[test]
+fn clearing_returns_the_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::do_something(Origin::signed(2), 42));
+
+		assert_ok!(TemplateModule::clear_something(Origin::signed(1)));
+		assert_ok!(TemplateModule::force_clear(Origin::root(), 2));
+
+		for who in &[1, 2] {
+			assert_eq!(Balances::reserved_balance(who), 0);
+			assert_eq!(Balances::free_balance(who), ENDOWMENT);
+			assert_eq!(TemplateModule::deposit_of(who), 0);
+		}
+	});
+}
+
+#[test]
+fn expiry_returns_the_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+
+		// `ValueLifetime` is 5 in the mock.
+		run_to_block(6);
+
+		assert_eq!(TemplateModule::something(1), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn values_set_by_admin_hold_no_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::force_set(Origin::root(), 1, 42));
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 43));
+		assert_eq!(Balances::reserved_balance(1), ValueDeposit::get());
+	});
+}
+
+#[test]
+fn history_is_bounded_by_history_depth() {
+	new_test_ext().execute_with(|| {
+		for value in 1..=5 {
+			assert_ok!(TemplateModule::do_something(Origin::signed(1), value));
+		}
+
+		// `HistoryDepth` is 3 in the mock, so the two oldest values were dropped.
+		assert_eq!(TemplateModule::something(1), Some(5));
+		assert_eq!(TemplateModule::something_history(1), vec![2, 3, 4]);
+		assert!(TemplateModule::something_history(2).is_empty());
+	});
+}
+
+#[test]
+fn clear_something_removes_value_and_history() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 1));
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 2));
+
+		assert_ok!(TemplateModule::clear_something(Origin::signed(1)));
+		assert_eq!(TemplateModule::something(1), None);
+		assert!(TemplateModule::something_history(1).is_empty());
+
+		assert_noop!(
+			TemplateModule::clear_something(Origin::signed(1)),
+			Error::<Test>::NoneValue
+		);
+	});
+}
+
+#[test]
+fn values_expire_after_value_lifetime() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_eq!(TemplateModule::last_updated(1), Some(1));
+
+		// `ValueLifetime` is 5 in the mock.
+		run_to_block(5);
+		assert_eq!(TemplateModule::something(1), Some(42));
+
+		run_to_block(6);
+		assert_eq!(TemplateModule::something(1), None);
+		assert_eq!(TemplateModule::last_updated(1), None);
+		assert_eq!(last_event(), TestEvent::pallet_template(RawEvent::SomethingExpired(1)));
+	});
+}
+
+#[test]
+fn rewriting_a_value_extends_its_lifetime() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+
+		run_to_block(4);
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 43));
+
+		// The entry scheduled at block 1 is skipped because the value was rewritten.
+		run_to_block(6);
+		assert_eq!(TemplateModule::something(1), Some(43));
+
+		run_to_block(9);
+		assert_eq!(TemplateModule::something(1), None);
+		// The previous values are removed with the value, as nothing pays for them anymore.
+		assert!(TemplateModule::something_history(1).is_empty());
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn raising_the_lifetime_reschedules_stored_values() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+
+		// The value was scheduled to expire at block 6.
+		set_value_lifetime(10);
+		run_to_block(6);
+		assert_eq!(TemplateModule::something(1), Some(42));
+
+		run_to_block(10);
+		assert_eq!(TemplateModule::something(1), Some(42));
+
+		run_to_block(11);
+		assert_eq!(TemplateModule::something(1), None);
+		assert_eq!(last_event(), TestEvent::pallet_template(RawEvent::SomethingExpired(1)));
+	});
+}
+
+#[test]
+fn a_zero_lifetime_keeps_scheduled_values() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+
+		set_value_lifetime(0);
+		run_to_block(6);
+		assert_eq!(TemplateModule::something(1), Some(42));
+		assert_eq!(TemplateModule::deposit_of(1), ValueDeposit::get());
+
+		// The entry was dropped, so the value is kept even once expiry is enabled again.
+		set_value_lifetime(5);
+		run_to_block(20);
+		assert_eq!(TemplateModule::something(1), Some(42));
+	});
+}
+
+#[test]
+fn on_initialize_weight_depends_on_due_entries() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 1));
+		assert_ok!(TemplateModule::do_something(Origin::signed(2), 2));
+
+		assert_eq!(TemplateModule::on_initialize(2), <() as WeightInfo>::on_initialize(0));
+		assert_eq!(TemplateModule::on_initialize(6), <() as WeightInfo>::on_initialize(2));
+	});
+}
+
+#[test]
+fn genesis_seeds_initial_values() {
+	ExtBuilder::default().initial_values(vec![(1, 10), (2, 20)]).build().execute_with(|| {
+		assert_eq!(TemplateModule::something(1), Some(10));
+		assert_eq!(TemplateModule::something(2), Some(20));
+		assert_eq!(TemplateModule::last_updated(1), Some(0));
+		assert!(TemplateModule::something_history(1).is_empty());
+	});
+}
+
+#[test]
+fn instances_start_from_their_own_genesis() {
+	ExtBuilder::default()
+		.initial_values(vec![(1, 10)])
+		.authorities(vec![7])
+		.second_instance(vec![(1, 15), (2, 25)], vec![8])
+		.build()
+		.execute_with(|| {
+			assert_eq!(TemplateModule::something(1), Some(10));
+			assert_eq!(TemplateModule::something(2), None);
+			assert_eq!(TemplateModule::authorities(), vec![7]);
+
+			assert_eq!(SecondTemplateModule::something(1), Some(15));
+			assert_eq!(SecondTemplateModule::something(2), Some(25));
+			assert_eq!(SecondTemplateModule::authorities(), vec![8]);
+		});
+}
+
+#[test]
+#[should_panic(expected = "Account appears more than once in `initial_values`")]
+fn genesis_rejects_duplicate_accounts() {
+	ExtBuilder::default().initial_values(vec![(1, 10), (1, 20)]).build();
+}
+
+#[test]
+fn admin_calls_require_admin_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(TemplateModule::force_set(Origin::signed(1), 2, 42), DispatchError::BadOrigin);
+		assert_noop!(TemplateModule::force_clear(Origin::signed(1), 2), DispatchError::BadOrigin);
+		assert_noop!(TemplateModule::set_paused(Origin::signed(1), true), DispatchError::BadOrigin);
+	});
+}
+
+#[test]
+fn force_set_and_force_clear_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::force_set(Origin::root(), 2, 42));
+		assert_eq!(TemplateModule::something(2), Some(42));
+
+		assert_ok!(TemplateModule::force_clear(Origin::root(), 2));
+		assert_eq!(TemplateModule::something(2), None);
+
+		assert_noop!(TemplateModule::force_clear(Origin::root(), 2), Error::<Test>::NoneValue);
+		assert_events!(
+			TestEvent::pallet_template(RawEvent::SomethingStored(42, 2)),
+			TestEvent::pallet_template(RawEvent::SomethingCleared(2)),
+		);
+	});
+}
+
+#[test]
+fn paused_pallet_rejects_signed_calls() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::set_paused(Origin::root(), true));
+
+		assert_noop!(TemplateModule::do_something(Origin::signed(1), 43), Error::<Test>::Paused);
+		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::Paused);
+		// Admin calls and clearing one's own value still work while paused.
+		assert_ok!(TemplateModule::force_set(Origin::root(), 2, 7));
+		assert_ok!(TemplateModule::clear_something(Origin::signed(1)));
+
+		assert_ok!(TemplateModule::set_paused(Origin::root(), false));
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 43));
+	});
+}
+
+#[test]
+fn offchain_worker_submits_stored_value() {
+	let (offchain, _offchain_state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(OffchainExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+	UintAuthorityId::set_all_keys(vec![7]);
+
+	t.execute_with(|| {
+		// Nothing is submitted while offchain storage is empty.
+		TemplateModule::offchain_worker(1);
+		assert!(pool_state.read().transactions.is_empty());
+
+		StorageValueRef::persistent(&TemplateModule::offchain_key(OFFCHAIN_VALUE_KEY)).set(&42u32);
+		TemplateModule::offchain_worker(2);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, Some((7, ())));
+		assert_eq!(tx.call, OuterCall::TemplateModule(crate::Call::do_something(42)));
+
+		// The value is consumed once it has been submitted.
+		assert_eq!(StorageValueRef::persistent(&TemplateModule::offchain_key(OFFCHAIN_VALUE_KEY)).get::<u32>(), None);
+	});
+}
+
+#[test]
+fn offchain_worker_keeps_value_while_paused() {
+	let (offchain, _offchain_state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(OffchainExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+	UintAuthorityId::set_all_keys(vec![7]);
+
+	t.execute_with(|| {
+		assert_ok!(TemplateModule::set_paused(Origin::root(), true));
+		StorageValueRef::persistent(&TemplateModule::offchain_key(OFFCHAIN_VALUE_KEY)).set(&42u32);
+
+		TemplateModule::offchain_worker(1);
+
+		assert!(pool_state.read().transactions.is_empty());
+		assert_eq!(StorageValueRef::persistent(&TemplateModule::offchain_key(OFFCHAIN_VALUE_KEY)).get::<u32>(), Some(Some(42)));
+	});
+}
+
+#[test]
+fn submit_something_unsigned_stores_for_signer() {
+	new_test_ext().execute_with(|| {
+		let (payload, signature) = signed_payload(7, 1, 42);
+
+		assert_noop!(
+			TemplateModule::submit_something_unsigned(Origin::signed(7), payload.clone(), signature.clone()),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(TemplateModule::submit_something_unsigned(Origin::none(), payload, signature));
+
+		assert_eq!(TemplateModule::something(7), Some(42));
+		assert_eq!(last_event(), TestEvent::pallet_template(RawEvent::SomethingStored(42, 7)));
+		// `UnsignedInterval` is 3 in the mock.
+		assert_eq!(TemplateModule::next_unsigned_at(7), 4);
+	});
+}
+
+#[test]
+fn validate_unsigned_accepts_signed_payload() {
+	new_test_ext().execute_with(|| {
+		let (payload, signature) = signed_payload(7, 1, 42);
+
+		let valid = validate(payload, signature).unwrap();
+		assert_eq!(valid.priority, 100);
+		assert_eq!(valid.longevity, 3);
+		assert_eq!(valid.provides, vec![("TemplateModule", (7u64, 1u64)).encode()]);
+		assert!(valid.propagate);
+	});
+}
+
+#[test]
+fn validate_unsigned_rejects_invalid_payloads() {
+	new_test_ext().execute_with(|| {
+		run_to_block(2);
+
+		// Signed by a different key than the one in the payload.
+		let (payload, _) = signed_payload(7, 2, 42);
+		let (_, signature) = signed_payload(8, 2, 42);
+		assert_eq!(validate(payload, signature), InvalidTransaction::BadProof.into());
+
+		// Created at a block that has not been reached yet.
+		let (payload, signature) = signed_payload(7, 3, 42);
+		assert_eq!(validate(payload, signature), InvalidTransaction::Future.into());
+
+		// Rejected while the pallet is paused.
+		assert_ok!(TemplateModule::set_paused(Origin::root(), true));
+		let (payload, signature) = signed_payload(7, 2, 42);
+		assert_eq!(validate(payload, signature), InvalidTransaction::Call.into());
+	});
+}
+
+#[test]
+fn validate_unsigned_rejects_non_authorities() {
+	ExtBuilder::default().authorities(vec![]).build().execute_with(|| {
+		let (payload, signature) = signed_payload(7, 1, 42);
+		assert_eq!(validate(payload, signature), InvalidTransaction::BadProof.into());
+	});
+
+	new_test_ext().execute_with(|| {
+		// A fresh key with a valid signature is not enough.
+		let (payload, signature) = signed_payload(8, 1, 42);
+		assert_eq!(validate(payload, signature), InvalidTransaction::BadProof.into());
+	});
+}
+
+#[test]
+fn admin_sets_authorities() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(TemplateModule::set_authorities(Origin::signed(7), vec![8]), DispatchError::BadOrigin);
+
+		assert_ok!(TemplateModule::set_authorities(Origin::root(), vec![8]));
+		assert_eq!(TemplateModule::authorities(), vec![8]);
+		assert_eq!(last_event(), TestEvent::pallet_template(RawEvent::AuthoritiesSet));
+
+		let (payload, signature) = signed_payload(8, 1, 42);
+		assert!(validate(payload, signature).is_ok());
+		let (payload, signature) = signed_payload(7, 1, 42);
+		assert_eq!(validate(payload, signature), InvalidTransaction::BadProof.into());
+	});
+}
+
+#[test]
+fn validate_unsigned_rejects_replayed_payloads() {
+	new_test_ext().execute_with(|| {
+		let (payload, signature) = signed_payload(7, 1, 42);
+		assert_ok!(TemplateModule::submit_something_unsigned(Origin::none(), payload.clone(), signature.clone()));
+
+		run_to_block(2);
+		assert_eq!(validate(payload, signature), InvalidTransaction::Stale.into());
+
+		// A payload created before the interval has passed is stale as well.
+		let (payload, signature) = signed_payload(7, 3, 43);
+		run_to_block(3);
+		assert_eq!(validate(payload, signature), InvalidTransaction::Stale.into());
+
+		let (payload, signature) = signed_payload(7, 4, 43);
+		run_to_block(4);
+		assert!(validate(payload, signature).is_ok());
+	});
+}
+
+#[test]
+fn offchain_worker_submits_unsigned_value_with_signed_payload() {
+	let (offchain, _offchain_state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(OffchainExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+	UintAuthorityId::set_all_keys(vec![7]);
+
+	t.execute_with(|| {
+		StorageValueRef::persistent(&TemplateModule::offchain_key(OFFCHAIN_UNSIGNED_VALUE_KEY)).set(&42u32);
+		TemplateModule::offchain_worker(1);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, None);
+		if let OuterCall::TemplateModule(crate::Call::submit_something_unsigned(payload, signature)) = tx.call {
+			assert_eq!(payload, SomethingPayload { block_number: 1, value: 42, public: UintAuthorityId(7) });
+			assert!(payload.public.verify(&payload.encode(), &signature));
+		} else {
+			panic!("Unexpected call: {:?}", tx.call);
+		}
+
+		assert_eq!(StorageValueRef::persistent(&TemplateModule::offchain_key(OFFCHAIN_UNSIGNED_VALUE_KEY)).get::<u32>(), None);
+	});
+}
+
+#[test]
+fn statistics_aggregate_stored_values() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(TemplateModule::statistics(), SomethingStatistics::default());
+
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 10));
+		assert_ok!(TemplateModule::do_something(Origin::signed(2), 3));
+		assert_ok!(TemplateModule::do_something(Origin::signed(3), 29));
+		// Only the current value of each account counts.
+		assert_ok!(TemplateModule::do_something(Origin::signed(3), 30));
+
+		assert_eq!(TemplateModule::statistics(), SomethingStatistics {
+			accounts: 3,
+			total: 43,
+			min: Some(3),
+			max: Some(30),
+		});
+	});
+}
+
+#[test]
+fn genesis_starts_at_latest_storage_version() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(<StorageVersion>::get(), Releases::V2);
+	});
+}
+
+#[test]
+fn migration_moves_legacy_value_to_owner() {
+	ExtBuilder::default().build_v1(Some(42)).execute_with(|| {
+		run_to_block(3);
+		let legacy = migrations::pre_migrate_to_v2::<Test, DefaultInstance>().unwrap();
+		assert_eq!(legacy, Some(42));
+
+		let weight = TemplateModule::on_runtime_upgrade();
+
+		assert_ok!(migrations::post_migrate_to_v2::<Test, DefaultInstance>(legacy));
+		// `LegacyValueOwner` is 100 in the mock.
+		assert_eq!(TemplateModule::something(100), Some(42));
+		assert_eq!(TemplateModule::last_updated(100), Some(3));
+		assert_eq!(weight, <() as WeightInfo>::force_set() + DbWeight::get().reads_writes(3, 2));
+	});
+}
+
+#[test]
+fn migration_keeps_owner_value() {
+	ExtBuilder::default().build_v1(Some(42)).execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(100), 7));
+
+		TemplateModule::on_runtime_upgrade();
+
+		assert_ok!(migrations::post_migrate_to_v2::<Test, DefaultInstance>(Some(42)));
+		assert_eq!(TemplateModule::something(100), Some(7));
+	});
+}
+
+#[test]
+fn migration_without_legacy_value_only_bumps_version() {
+	ExtBuilder::default().build_v1(None).execute_with(|| {
+		let legacy = migrations::pre_migrate_to_v2::<Test, DefaultInstance>().unwrap();
+
+		TemplateModule::on_runtime_upgrade();
+
+		assert_ok!(migrations::post_migrate_to_v2::<Test, DefaultInstance>(legacy));
+		assert_eq!(<StorageVersion>::get(), Releases::V2);
+		assert_eq!(TemplateModule::statistics().accounts, 0);
+	});
+}
+
+#[test]
+fn migration_is_idempotent() {
+	ExtBuilder::default().build_v1(Some(42)).execute_with(|| {
+		TemplateModule::on_runtime_upgrade();
+		assert_ok!(TemplateModule::do_something(Origin::signed(100), 7));
+
+		// The second run only reads the storage version.
+		assert_eq!(TemplateModule::on_runtime_upgrade(), DbWeight::get().reads(1));
+		assert_eq!(TemplateModule::something(100), Some(7));
+		assert_eq!(TemplateModule::something_history(100), vec![42]);
+		assert!(migrations::pre_migrate_to_v2::<Test, DefaultInstance>().is_err());
+	});
+}
+
+#[test]
+fn instances_have_separate_storage() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_ok!(SecondTemplateModule::do_something(Origin::signed(1), 7));
+		assert_ok!(SecondTemplateModule::do_something(Origin::signed(2), 8));
+
+		assert_eq!(TemplateModule::something(1), Some(42));
+		assert_eq!(TemplateModule::something(2), None);
+		assert_eq!(SecondTemplateModule::something(1), Some(7));
+		assert_eq!(SecondTemplateModule::something(2), Some(8));
+
+		// Each instance deposits its own events.
+		let events: Vec<_> = System::events()
+			.into_iter()
+			.map(|record| record.event)
+			.filter(|event| matches!(
+				event,
+				TestEvent::pallet_template(_) | TestEvent::pallet_template_Instance1(_)
+			))
+			.collect();
+		assert_eq!(events, vec![
+			TestEvent::pallet_template(RawEvent::SomethingStored(42, 1)),
+			TestEvent::pallet_template_Instance1(RawEvent::SomethingStored(7, 1)),
+			TestEvent::pallet_template_Instance1(RawEvent::SomethingStored(8, 2)),
+		]);
+
+		// Clearing or pausing one instance leaves the other untouched.
+		assert_ok!(TemplateModule::clear_something(Origin::signed(1)));
+		assert_ok!(SecondTemplateModule::set_paused(Origin::root(), true));
+		assert_eq!(TemplateModule::something(1), None);
+		assert_eq!(SecondTemplateModule::something(1), Some(7));
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 43));
+		assert_noop!(
+			SecondTemplateModule::do_something(Origin::signed(1), 9),
+			Error::<Test, crate::Instance1>::Paused
+		);
+	});
+}
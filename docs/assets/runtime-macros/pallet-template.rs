@@ -9,8 +9,35 @@
 /// For more guidance on Substrate FRAME, see the example pallet
 /// https://github.com/paritytech/substrate/blob/master/frame/example/src/lib.rs
 
-use frame_support::{decl_module, decl_storage, decl_event, decl_error, dispatch};
-use frame_system::{self as system, ensure_signed};
+use codec::{Codec, Encode, Decode};
+use frame_support::{
+	debug, decl_module, decl_storage, decl_event, decl_error, dispatch, ensure, Parameter,
+	storage::IterableStorageMap,
+	traits::{Currency, Get, EnsureOrigin, Instance, ReservableCurrency},
+	weights::Weight,
+};
+use frame_system::{
+	self as system, ensure_signed, ensure_none,
+	offchain::{
+		AppCrypto, CreateSignedTransaction, SendSignedTransaction, SendUnsignedTransaction,
+		SignedPayload, Signer, SigningTypes,
+	},
+};
+use sp_runtime::{
+	RuntimeDebug, SaturatedConversion,
+	offchain::storage::StorageValueRef,
+	traits::{
+		AtLeast32BitUnsigned, CheckedAdd, CheckedSub, IdentifyAccount, MaybeSerializeDeserialize,
+		Member, Saturating, Zero,
+	},
+	transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity,
+		ValidTransaction,
+	},
+};
+use sp_std::prelude::*;
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
 
 #[cfg(test)]
 mod mock;
@@ -18,71 +45,327 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+pub mod migrations;
+
+mod app_crypto;
+pub use app_crypto::{KEY_TYPE, crypto};
+
+/// The offchain local storage key read by the offchain worker.
+///
+/// When a SCALE-encoded `Value` is found under this key (for example, written through the
+/// `offchain_localStorageSet` RPC), the offchain worker submits it with a signed
+/// `do_something` transaction and removes it.
+///
+/// Each instance of the pallet reads this key prefixed with its storage prefix, see
+/// `Module::offchain_key`.
+pub const OFFCHAIN_VALUE_KEY: &[u8] = b"::something";
+
+/// The offchain local storage key for values the offchain worker submits without fees.
+///
+/// Works like `OFFCHAIN_VALUE_KEY`, but the value is sent with an unsigned
+/// `submit_something_unsigned` transaction carrying a payload signed by the local key.
+/// The key's account must be one of the pallet's `Authorities`.
+pub const OFFCHAIN_UNSIGNED_VALUE_KEY: &[u8] = b"::something_unsigned";
+
+/// The payload of `submit_something_unsigned`, signed by the key of the account it stores for.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SomethingPayload<Public, BlockNumber, Value> {
+	/// The block at which the payload was created.
+	pub block_number: BlockNumber,
+	/// The value to store.
+	pub value: Value,
+	/// The key which signed the payload. The value is stored for its account.
+	pub public: Public,
+}
+
+/// Aggregate statistics over the values currently stored, as returned by `statistics`.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct SomethingStatistics<Value> {
+	/// The number of accounts with a value.
+	pub accounts: u32,
+	/// The sum of all values, saturating at `u64::MAX`.
+	pub total: u64,
+	/// The smallest value, if any is stored.
+	pub min: Option<Value>,
+	/// The largest value, if any is stored.
+	pub max: Option<Value>,
+}
+
+impl<T: SigningTypes, Value: Encode> SignedPayload<T>
+	for SomethingPayload<T::Public, T::BlockNumber, Value>
+{
+	fn public(&self) -> T::Public {
+		self.public.clone()
+	}
+}
+
+type BalanceOf<T, I = DefaultInstance> =
+	<<T as Trait<I>>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
+
 /// The pallet's configuration trait.
-pub trait Trait: system::Trait {
+pub trait Trait<I: Instance = DefaultInstance>: CreateSignedTransaction<Call<Self, I>> {
 	// Add other types and constants required to configure this pallet.
 
+	/// The identifier type for the offchain worker's signing key.
+	type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
 	/// The overarching event type.
-	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+	type Event: From<Event<Self, I>> + Into<<Self as system::Trait>::Event>;
+
+	/// The type of the value stored for each account.
+	type Value: Parameter + Member + AtLeast32BitUnsigned + Codec + Default + Copy +
+		MaybeSerializeDeserialize;
+
+	/// The amount `cause_error` adds to the caller's value.
+	type Step: Get<Self::Value>;
+
+	/// The currency in which value deposits are reserved.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// The amount reserved from an account when `do_something` first stores a value for it.
+	/// It is returned when the value is cleared or expires.
+	type ValueDeposit: Get<BalanceOf<Self, I>>;
+
+	/// The maximum number of previous values kept in each account's history.
+	type HistoryDepth: Get<u32>;
+
+	/// The number of blocks a value stays in storage after it was last written.
+	/// A lifetime of zero disables expiry: values stored, or due to expire, while it is zero
+	/// are kept. Values already stored when it is raised live for the new lifetime; when it
+	/// is lowered, they expire at the block they were scheduled for.
+	type ValueLifetime: Get<Self::BlockNumber>;
+
+	/// The origin which may force-set or clear any account's value and pause the pallet.
+	type AdminOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The priority of `submit_something_unsigned` transactions in the pool.
+	type UnsignedPriority: Get<TransactionPriority>;
+
+	/// The number of blocks an account must wait between two `submit_something_unsigned`
+	/// transactions. This is also how long such a transaction stays valid in the pool.
+	type UnsignedInterval: Get<Self::BlockNumber>;
+
+	/// The account that the single value stored by version 1 of this pallet is migrated to.
+	type LegacyValueOwner: Get<Self::AccountId>;
+
+	/// Weight information for the extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+/// The storage layouts of this pallet, used to decide which migrations to run.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+enum Releases {
+	/// A single `Something: u32` value shared by all accounts.
+	V1,
+	/// Per-account values with their history and the block they were written at.
+	V2,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1
+	}
 }
 
 // This pallet's storage items.
 decl_storage! {
 	// It is important to update your storage name so that your pallet's
 	// storage items are isolated from other pallets.
-	// ---------------------------------vvvvvvvvvvvvvv
-	trait Store for Module<T: Trait> as TemplateModule {
+	// Each instance gets its own prefix, the default instance uses this name.
+	// -------------------------------------------------------------------vvvvvvvvvvvvvv
+	trait Store for Module<T: Trait<I>, I: Instance = DefaultInstance> as TemplateModule {
 		// Just a dummy storage item.
-		// Here we are declaring a StorageValue, `Something` as a Option<u32>
-		// `get(fn something)` is the default getter which returns either the stored `u32` or `None` if nothing stored
-		Something get(fn something): Option<u32>;
+		// Here we are declaring a StorageMap, `Something`, from an `AccountId` to an Option<T::Value>
+		// `get(fn something)` is the default getter which returns either the stored `T::Value` or `None` if nothing stored
+		Something get(fn something): map hasher(blake2_128_concat) T::AccountId => Option<T::Value>;
+
+		// The previous values of `Something` for each account, oldest first.
+		// Holds at most `T::HistoryDepth` entries; the oldest one is dropped when it is full.
+		SomethingHistory get(fn something_history): map hasher(blake2_128_concat) T::AccountId => Vec<T::Value>;
+
+		// The block at which each account's value was last written.
+		LastUpdated get(fn last_updated): map hasher(blake2_128_concat) T::AccountId => Option<T::BlockNumber>;
+
+		// The accounts whose values are due to expire at a given block.
+		// Entries are not removed when a value is rewritten or cleared, so they are checked
+		// against `LastUpdated` before anything is expired.
+		Expiries: map hasher(twox_64_concat) T::BlockNumber => Vec<T::AccountId>;
+
+		// Whether `do_something` and `cause_error` are currently disabled by the admin.
+		Paused get(fn paused): bool;
+
+		// The deposit reserved from each account for its value, see `T::ValueDeposit`.
+		// Accounts whose value was not stored by themselves hold no deposit.
+		Deposits get(fn deposit_of): map hasher(blake2_128_concat) T::AccountId => BalanceOf<T, I>;
+
+		// The first block at which each account may have another unsigned value included.
+		// Payloads created before this block are rejected, which prevents them being replayed.
+		NextUnsignedAt get(fn next_unsigned_at): map hasher(blake2_128_concat) T::AccountId => T::BlockNumber;
+
+		// The accounts whose keys may sign payloads for `submit_something_unsigned`.
+		// Unsigned values pay no fee or deposit, so they are only accepted from these accounts.
+		Authorities get(fn authorities) config(): Vec<T::AccountId>;
+
+		// The storage layout in use. Chains started before this item existed read the
+		// default `V1`, new chains start at the latest layout.
+		StorageVersion build(|_: &GenesisConfig<T, I>| Releases::V2): Releases;
+	}
+	add_extra_genesis {
+		// The values to store for each account at genesis.
+		// Each account may appear only once.
+		config(initial_values): Vec<(T::AccountId, T::Value)>;
+		build(|config: &GenesisConfig<T, I>| {
+			let mut seen = sp_std::collections::btree_set::BTreeSet::new();
+			for (who, value) in &config.initial_values {
+				assert!(seen.insert(who), "Account appears more than once in `initial_values`");
+				<Module<T, I>>::store(who, *value);
+			}
+		});
 	}
 }
 
 // The pallet's events
 decl_event!(
-	pub enum Event<T> where AccountId = <T as system::Trait>::AccountId {
+	pub enum Event<T, I = DefaultInstance> where
+		AccountId = <T as system::Trait>::AccountId,
+		Value = <T as Trait<I>>::Value,
+	{
 		/// Just a dummy event.
-		/// Event `Something` is declared with a parameter of the type `u32` and `AccountId`
+		/// Event `Something` is declared with a parameter of the type `Value` and `AccountId`
 		/// To emit this event, we call the deposit function, from our runtime functions
-		SomethingStored(u32, AccountId),
+		SomethingStored(Value, AccountId),
+		/// The value stored for an account was cleared, along with its history.
+		SomethingCleared(AccountId),
+		/// The value stored for an account reached the end of its lifetime and was removed,
+		/// along with its history.
+		SomethingExpired(AccountId),
+		/// The pallet was paused by the admin origin.
+		Paused,
+		/// The pallet was unpaused by the admin origin.
+		Unpaused,
+		/// The accounts which may submit unsigned values were replaced by the admin origin.
+		AuthoritiesSet,
 	}
 );
 
 // The pallet's errors
 decl_error! {
-	pub enum Error for Module<T: Trait> {
+	pub enum Error for Module<T: Trait<I>, I: Instance> {
 		/// Value was None
 		NoneValue,
 		/// Value reached maximum and cannot be incremented further
 		StorageOverflow,
+		/// Value reached zero and cannot be decremented further
+		StorageUnderflow,
+		/// The pallet is paused by the admin origin
+		Paused,
+		/// The account cannot afford the deposit for storing a value
+		InsufficientDeposit,
 	}
 }
 
 // The pallet's dispatchable functions.
 decl_module! {
 	/// The module declaration.
-	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+	pub struct Module<T: Trait<I>, I: Instance = DefaultInstance> for enum Call where origin: T::Origin {
 		// Initializing errors
 		// this includes information about your errors in the node's metadata.
 		// it is needed only if you are using errors in your pallet
-		type Error = Error<T>;
+		type Error = Error<T, I>;
 
 		// Initializing events
 		// this is needed only if you are using events in your pallet
 		fn deposit_event() = default;
 
+		/// The maximum number of previous values kept in each account's history.
+		const HistoryDepth: u32 = T::HistoryDepth::get();
+
+		/// The number of blocks a value stays in storage after it was last written.
+		const ValueLifetime: T::BlockNumber = T::ValueLifetime::get();
+
+		/// The amount `cause_error` adds to the caller's value.
+		const Step: T::Value = T::Step::get();
+
+		/// The amount reserved from an account when it first stores a value.
+		const ValueDeposit: BalanceOf<T, I> = T::ValueDeposit::get();
+
+		/// The number of blocks an account must wait between two unsigned submissions.
+		const UnsignedInterval: T::BlockNumber = T::UnsignedInterval::get();
+
+		/// Migrates storage written by earlier versions of this pallet.
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate_to_v2::<T, I>()
+		}
+
+		/// Removes the values whose lifetime ends at this block, along with their history.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let due = <Expiries<T, I>>::take(now);
+			let lifetime = T::ValueLifetime::get();
+			if lifetime.is_zero() {
+				return T::WeightInfo::on_initialize(0);
+			}
+			let count = due.len() as u32;
+
+			for who in due {
+				// Skip accounts whose value was cleared after this entry was scheduled.
+				let deadline = match <LastUpdated<T, I>>::get(&who) {
+					Some(at) => at.saturating_add(lifetime),
+					None => continue,
+				};
+				if deadline > now {
+					// The value was rewritten, or the lifetime raised, since this entry was
+					// scheduled. A rewrite has already scheduled its own entry.
+					<Expiries<T, I>>::mutate(deadline, |accounts| {
+						if !accounts.contains(&who) {
+							accounts.push(who);
+						}
+					});
+				} else {
+					// The history goes with the value, since the deposit covering it is returned.
+					<Something<T, I>>::remove(&who);
+					<SomethingHistory<T, I>>::remove(&who);
+					<LastUpdated<T, I>>::remove(&who);
+					Self::release_deposit(&who);
+					Self::deposit_event(RawEvent::SomethingExpired(who));
+				}
+			}
+
+			T::WeightInfo::on_initialize(count)
+		}
+
+		/// Submits the values found in offchain local storage, if any.
+		fn offchain_worker(block_number: T::BlockNumber) {
+			if let Err(e) = Self::submit_offchain_value() {
+				debug::warn!("Template offchain worker failed at block {:?}: {}", block_number, e);
+			}
+			if let Err(e) = Self::submit_offchain_value_unsigned(block_number) {
+				debug::warn!("Template offchain worker failed at block {:?}: {}", block_number, e);
+			}
+		}
+
 		/// Just a dummy entry point.
 		/// function that can be called by the external world as an extrinsics call
 		/// takes a parameter of the type `AccountId`, stores it, and emits an event
-		#[weight = 10_000]
-		pub fn do_something(origin, something: u32) -> dispatch::DispatchResult {
+		#[weight = T::WeightInfo::do_something()]
+		pub fn do_something(origin, something: T::Value) -> dispatch::DispatchResult {
 			// Check it was signed and get the signer. See also: ensure_root and ensure_none
 			let who = ensure_signed(origin)?;
+			ensure!(!Self::paused(), Error::<T, I>::Paused);
+
+			// The caller pays a deposit for the storage it uses, unless it already holds one.
+			Self::reserve_deposit(&who)?;
 
 			// Code to execute when something calls this.
-			// For example: the following line stores the passed in u32 in the storage
-			Something::put(something);
+			// For example: the following line stores the passed in value in the caller's storage
+			Self::store(&who, something);
 
 			// Here we are raising the Something event
 			Self::deposit_event(RawEvent::SomethingStored(something, who));
@@ -90,20 +373,358 @@ decl_module! {
 		}
 
 		/// Another dummy entry point.
-		/// takes no parameters, attempts to increment storage value, and possibly throws an error
-		#[weight = 10_000]
+		/// takes no parameters, attempts to increment storage value by `T::Step`, and possibly throws an error
+		#[weight = T::WeightInfo::cause_error()]
 		pub fn cause_error(origin) -> dispatch::DispatchResult {
 			// Check it was signed and get the signer. See also: ensure_root and ensure_none
-			let _who = ensure_signed(origin)?;
-
-			match Something::get() {
-				None => Err(Error::<T>::NoneValue)?,
-				Some(old) => {
-					let new = old.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
-					Something::put(new);
-					Ok(())
+			let who = ensure_signed(origin)?;
+			ensure!(!Self::paused(), Error::<T, I>::Paused);
+
+			Self::update(&who, |old| {
+				old.checked_add(&T::Step::get()).ok_or(Error::<T, I>::StorageOverflow)
+			})?;
+			Ok(())
+		}
+
+		/// Adds `amount` to the caller's value, failing if the result does not fit in `T::Value`.
+		#[weight = T::WeightInfo::increment_by()]
+		pub fn increment_by(origin, amount: T::Value) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!Self::paused(), Error::<T, I>::Paused);
+
+			let new = Self::update(&who, |old| {
+				old.checked_add(&amount).ok_or(Error::<T, I>::StorageOverflow)
+			})?;
+
+			Self::deposit_event(RawEvent::SomethingStored(new, who));
+			Ok(())
+		}
+
+		/// Subtracts `amount` from the caller's value, failing if the result would be below zero.
+		#[weight = T::WeightInfo::decrement_by()]
+		pub fn decrement_by(origin, amount: T::Value) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!Self::paused(), Error::<T, I>::Paused);
+
+			let new = Self::update(&who, |old| {
+				old.checked_sub(&amount).ok_or(Error::<T, I>::StorageUnderflow)
+			})?;
+
+			Self::deposit_event(RawEvent::SomethingStored(new, who));
+			Ok(())
+		}
+
+		/// Adds `amount` to the caller's value, stopping at the largest `T::Value`.
+		#[weight = T::WeightInfo::saturating_increment_by()]
+		pub fn saturating_increment_by(origin, amount: T::Value) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!Self::paused(), Error::<T, I>::Paused);
+
+			let new = Self::update(&who, |old| Ok(old.saturating_add(amount)))?;
+
+			Self::deposit_event(RawEvent::SomethingStored(new, who));
+			Ok(())
+		}
+
+		/// Subtracts `amount` from the caller's value, stopping at zero.
+		#[weight = T::WeightInfo::saturating_decrement_by()]
+		pub fn saturating_decrement_by(origin, amount: T::Value) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!Self::paused(), Error::<T, I>::Paused);
+
+			let new = Self::update(&who, |old| Ok(old.saturating_sub(amount)))?;
+
+			Self::deposit_event(RawEvent::SomethingStored(new, who));
+			Ok(())
+		}
+
+		/// Removes the caller's value and its history, and returns its deposit.
+		/// This is allowed while the pallet is paused, so accounts can always remove their data.
+		#[weight = T::WeightInfo::clear_something()]
+		pub fn clear_something(origin) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::clear(who)
+		}
+
+		/// Stores the value of a signed payload for the account of the key that signed it.
+		///
+		/// This is an unsigned transaction, so no fees are paid. The signature is checked when
+		/// the transaction is validated, see the `ValidateUnsigned` implementation.
+		#[weight = T::WeightInfo::submit_something_unsigned()]
+		pub fn submit_something_unsigned(
+			origin,
+			payload: SomethingPayload<T::Public, T::BlockNumber, T::Value>,
+			_signature: T::Signature,
+		) -> dispatch::DispatchResult {
+			ensure_none(origin)?;
+			ensure!(!Self::paused(), Error::<T, I>::Paused);
+
+			let who = payload.public.into_account();
+			let now = <system::Module<T>>::block_number();
+			<NextUnsignedAt<T, I>>::insert(&who, now.saturating_add(T::UnsignedInterval::get()));
+
+			Self::store(&who, payload.value);
+
+			Self::deposit_event(RawEvent::SomethingStored(payload.value, who));
+			Ok(())
+		}
+
+		/// Stores `something` for `who` on their behalf.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin`.
+		#[weight = T::WeightInfo::force_set()]
+		pub fn force_set(origin, who: T::AccountId, something: T::Value) -> dispatch::DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			Self::store(&who, something);
+
+			Self::deposit_event(RawEvent::SomethingStored(something, who));
+			Ok(())
+		}
+
+		/// Removes the value and history of `who`, and returns its deposit.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin`.
+		#[weight = T::WeightInfo::force_clear()]
+		pub fn force_clear(origin, who: T::AccountId) -> dispatch::DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::clear(who)
+		}
+
+		/// Replaces the accounts whose keys may sign payloads for `submit_something_unsigned`.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin`.
+		#[weight = T::WeightInfo::set_authorities(authorities.len() as u32)]
+		pub fn set_authorities(origin, authorities: Vec<T::AccountId>) -> dispatch::DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			<Authorities<T, I>>::put(authorities);
+
+			Self::deposit_event(RawEvent::AuthoritiesSet);
+			Ok(())
+		}
+
+		/// Pauses or unpauses the calls which change the caller's value.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin`.
+		#[weight = T::WeightInfo::set_paused()]
+		pub fn set_paused(origin, paused: bool) -> dispatch::DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			<Paused<I>>::put(paused);
+
+			if paused {
+				Self::deposit_event(RawEvent::Paused);
+			} else {
+				Self::deposit_event(RawEvent::Unpaused);
+			}
+			Ok(())
+		}
+	}
+}
+
+impl<T: Trait<I>, I: Instance> Module<T, I> {
+	/// Computes statistics over every stored value.
+	///
+	/// This iterates the whole `Something` map, so it is meant for runtime APIs and must not
+	/// be called from dispatchables. The `template_getStatistics` RPC serving it is unsafe.
+	pub fn statistics() -> SomethingStatistics<T::Value> {
+		<Something<T, I>>::iter().fold(SomethingStatistics::default(), |mut stats, (_, value)| {
+			stats.accounts += 1;
+			stats.total = stats.total.saturating_add(value.saturated_into::<u64>());
+			stats.min = Some(stats.min.map_or(value, |min| min.min(value)));
+			stats.max = Some(stats.max.map_or(value, |max| max.max(value)));
+			stats
+		})
+	}
+
+	/// Returns the offchain local storage key this instance reads for `key`, which is `key`
+	/// prefixed with the instance's storage prefix, e.g. `TemplateModule::something`.
+	pub fn offchain_key(key: &[u8]) -> Vec<u8> {
+		[I::PREFIX.as_bytes(), key].concat()
+	}
+
+	/// Sends a signed `do_something` with the value stored under `OFFCHAIN_VALUE_KEY`.
+	///
+	/// The value is left in place while the pallet is paused or when no transaction could
+	/// be sent, so that a later run can try again.
+	fn submit_offchain_value() -> Result<(), &'static str> {
+		let mut storage = StorageValueRef::persistent(&Self::offchain_key(OFFCHAIN_VALUE_KEY));
+		let value = match Self::offchain_value(&storage)? {
+			Some(value) => value,
+			None => return Ok(()),
+		};
+
+		let signer = Signer::<T, T::AuthorityId>::any_account();
+		let (_, result) = signer
+			.send_signed_transaction(|_account| Call::do_something(value))
+			.ok_or("No local account available to sign the transaction")?;
+		result.map_err(|()| "Unable to submit the signed transaction")?;
+
+		storage.clear();
+		Ok(())
+	}
+
+	/// Sends an unsigned `submit_something_unsigned` with the value stored under
+	/// `OFFCHAIN_UNSIGNED_VALUE_KEY`, in a payload signed by a local key.
+	///
+	/// Like `submit_offchain_value`, the value is only removed once it has been sent.
+	fn submit_offchain_value_unsigned(block_number: T::BlockNumber) -> Result<(), &'static str> {
+		let mut storage = StorageValueRef::persistent(&Self::offchain_key(OFFCHAIN_UNSIGNED_VALUE_KEY));
+		let value = match Self::offchain_value(&storage)? {
+			Some(value) => value,
+			None => return Ok(()),
+		};
+
+		let signer = Signer::<T, T::AuthorityId>::any_account();
+		let (_, result) = signer
+			.send_unsigned_transaction(
+				|account| SomethingPayload {
+					block_number,
+					value,
+					public: account.public.clone(),
 				},
+				|payload, signature| Call::submit_something_unsigned(payload, signature),
+			)
+			.ok_or("No local account available to sign the payload")?;
+		result.map_err(|()| "Unable to submit the unsigned transaction")?;
+
+		storage.clear();
+		Ok(())
+	}
+
+	/// Reads the value the offchain worker should submit from `storage`.
+	///
+	/// Returns `None` when there is nothing to submit, including while the pallet is paused.
+	fn offchain_value(storage: &StorageValueRef) -> Result<Option<T::Value>, &'static str> {
+		let value = match storage.get::<T::Value>() {
+			Some(Some(value)) => value,
+			Some(None) => return Err("Unable to decode the value in offchain storage"),
+			None => return Ok(None),
+		};
+
+		if Self::paused() {
+			return Ok(None);
+		}
+		Ok(Some(value))
+	}
+
+	/// Checks that `payload` is from an authority, and is neither from the future nor a
+	/// replay of an included one.
+	fn validate_something_payload(
+		payload: &SomethingPayload<T::Public, T::BlockNumber, T::Value>,
+	) -> TransactionValidity {
+		if Self::paused() {
+			return InvalidTransaction::Call.into();
+		}
+
+		// Anyone can create keys, so a valid signature alone does not limit who stores values.
+		let who = payload.public.clone().into_account();
+		if !Self::authorities().contains(&who) {
+			return InvalidTransaction::BadProof.into();
+		}
+
+		let now = <system::Module<T>>::block_number();
+		if payload.block_number > now {
+			return InvalidTransaction::Future.into();
+		}
+
+		if payload.block_number < Self::next_unsigned_at(&who) {
+			return InvalidTransaction::Stale.into();
+		}
+
+		let longevity = T::UnsignedInterval::get().saturated_into::<u64>().max(1);
+		ValidTransaction::with_tag_prefix(I::PREFIX)
+			.priority(T::UnsignedPriority::get())
+			// Only one payload per account and block may be in the pool.
+			.and_provides((who, payload.block_number))
+			.longevity(longevity)
+			.propagate(true)
+			.build()
+	}
+
+	/// Replaces the value of `who` with the result of `f`, failing if there is no value.
+	///
+	/// Returns the new value.
+	fn update(
+		who: &T::AccountId,
+		f: impl FnOnce(T::Value) -> Result<T::Value, Error<T, I>>,
+	) -> Result<T::Value, Error<T, I>> {
+		let old = <Something<T, I>>::get(who).ok_or(Error::<T, I>::NoneValue)?;
+		let new = f(old)?;
+		Self::store(who, new);
+		Ok(new)
+	}
+
+	/// Removes the value and history of `who`, failing if there is no value.
+	fn clear(who: T::AccountId) -> dispatch::DispatchResult {
+		let _ = <Something<T, I>>::take(&who).ok_or(Error::<T, I>::NoneValue)?;
+		<SomethingHistory<T, I>>::remove(&who);
+		<LastUpdated<T, I>>::remove(&who);
+		Self::release_deposit(&who);
+
+		Self::deposit_event(RawEvent::SomethingCleared(who));
+		Ok(())
+	}
+
+	/// Reserves `T::ValueDeposit` from `who`, unless it already holds a deposit.
+	fn reserve_deposit(who: &T::AccountId) -> dispatch::DispatchResult {
+		if <Deposits<T, I>>::contains_key(who) {
+			return Ok(());
+		}
+
+		let deposit = T::ValueDeposit::get();
+		T::Currency::reserve(who, deposit).map_err(|_| Error::<T, I>::InsufficientDeposit)?;
+		<Deposits<T, I>>::insert(who, deposit);
+		Ok(())
+	}
+
+	/// Returns the deposit held by `who`, if any.
+	fn release_deposit(who: &T::AccountId) {
+		let deposit = <Deposits<T, I>>::take(who);
+		if !deposit.is_zero() {
+			T::Currency::unreserve(who, deposit);
+		}
+	}
+
+	/// Stores `value` for `who`, pushing the value it replaces onto the account's history
+	/// and scheduling the new value to expire after `T::ValueLifetime` blocks.
+	fn store(who: &T::AccountId, value: T::Value) {
+		if let Some(old) = <Something<T, I>>::get(who) {
+			let depth = T::HistoryDepth::get() as usize;
+			<SomethingHistory<T, I>>::mutate(who, |history| {
+				history.push(old);
+				if history.len() > depth {
+					let excess = history.len() - depth;
+					history.drain(..excess);
+				}
+			});
+		}
+		<Something<T, I>>::insert(who, value);
+
+		let now = <system::Module<T>>::block_number();
+		<LastUpdated<T, I>>::insert(who, now);
+
+		let lifetime = T::ValueLifetime::get();
+		if !lifetime.is_zero() {
+			<Expiries<T, I>>::append(now.saturating_add(lifetime), who);
+		}
+	}
+}
+
+impl<T: Trait<I>, I: Instance> frame_support::unsigned::ValidateUnsigned for Module<T, I> {
+	type Call = Call<T, I>;
+
+	fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+		if let Call::submit_something_unsigned(ref payload, ref signature) = call {
+			let signature_valid =
+				SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone());
+			if !signature_valid {
+				return InvalidTransaction::BadProof.into();
 			}
+			Self::validate_something_payload(payload)
+		} else {
+			InvalidTransaction::Call.into()
 		}
 	}
 }
@@ -0,0 +1,42 @@
+//! Benchmarking setup for parameters
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::benchmarks;
+
+benchmarks! {
+	_ { }
+
+	set_existential_deposit {
+		let value = T::MaxExistentialDeposit::get();
+		let origin = T::SetOrigin::successful_origin();
+	}: { Module::<T>::set_existential_deposit(origin, value)?; }
+	verify {
+		assert_eq!(ExistentialDeposit::<T>::get(), value);
+	}
+
+	set_transaction_byte_fee {
+		let value = T::MaxTransactionByteFee::get();
+		let origin = T::SetOrigin::successful_origin();
+	}: { Module::<T>::set_transaction_byte_fee(origin, value)?; }
+	verify {
+		assert_eq!(TransactionByteFee::<T>::get(), value);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_existential_deposit::<Test>());
+			assert_ok!(test_benchmark_set_transaction_byte_fee::<Test>());
+		});
+	}
+}
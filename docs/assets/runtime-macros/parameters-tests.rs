@@ -0,0 +1,81 @@
+// Tests to be written here
+
+use crate::{Error, RawEvent, GenesisConfig, mock::*};
+use frame_support::{assert_ok, assert_noop, traits::Get};
+use sp_runtime::{BuildStorage, DispatchError};
+
+fn last_event() -> TestEvent {
+	System::events().pop().expect("An event was expected").event
+}
+
+#[test]
+fn parameters_start_at_their_defaults() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Parameters::existential_deposit(), 500);
+		assert_eq!(Parameters::transaction_byte_fee(), 1);
+	});
+}
+
+#[test]
+fn genesis_overrides_the_defaults() {
+	let t = GenesisConfig::<Test> {
+		existential_deposit: 1_000,
+		transaction_byte_fee: 2,
+	}.build_storage().unwrap();
+	sp_io::TestExternalities::from(t).execute_with(|| {
+		assert_eq!(Parameters::existential_deposit(), 1_000);
+		assert_eq!(Parameters::transaction_byte_fee(), 2);
+	});
+}
+
+#[test]
+#[should_panic(expected = "`existential_deposit` is zero or above `MaxExistentialDeposit`")]
+fn genesis_rejects_a_zero_existential_deposit() {
+	let _ = GenesisConfig::<Test> {
+		existential_deposit: 0,
+		transaction_byte_fee: 1,
+	}.build_storage();
+}
+
+#[test]
+#[should_panic(expected = "`transaction_byte_fee` is above `MaxTransactionByteFee`")]
+fn genesis_rejects_a_byte_fee_above_the_limit() {
+	let _ = GenesisConfig::<Test> {
+		existential_deposit: 500,
+		transaction_byte_fee: 101,
+	}.build_storage();
+}
+
+#[test]
+fn setters_update_storage_and_adapters() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Parameters::set_existential_deposit(Origin::root(), 1_000));
+		assert_eq!(crate::ExistentialDepositOf::<Test>::get(), 1_000);
+		assert_eq!(last_event(), TestEvent::parameters(RawEvent::ExistentialDepositSet(1_000)));
+
+		assert_ok!(Parameters::set_transaction_byte_fee(Origin::root(), 0));
+		assert_eq!(crate::TransactionByteFeeOf::<Test>::get(), 0);
+		assert_eq!(last_event(), TestEvent::parameters(RawEvent::TransactionByteFeeSet(0)));
+	});
+}
+
+#[test]
+fn setters_require_set_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Parameters::set_existential_deposit(Origin::signed(1), 1_000), DispatchError::BadOrigin);
+		assert_noop!(Parameters::set_transaction_byte_fee(Origin::signed(1), 2), DispatchError::BadOrigin);
+	});
+}
+
+#[test]
+fn setters_reject_values_out_of_bounds() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Parameters::set_existential_deposit(Origin::root(), 0), Error::<Test>::OutOfBounds);
+		assert_noop!(Parameters::set_existential_deposit(Origin::root(), 10_001), Error::<Test>::OutOfBounds);
+		assert_noop!(Parameters::set_transaction_byte_fee(Origin::root(), 101), Error::<Test>::OutOfBounds);
+
+		// The limits themselves are accepted.
+		assert_ok!(Parameters::set_existential_deposit(Origin::root(), 10_000));
+		assert_ok!(Parameters::set_transaction_byte_fee(Origin::root(), 100));
+	});
+}
@@ -0,0 +1,43 @@
+//! Weights for parameters
+//!
+//! These are hand-written placeholders, not benchmark results: each call is charged a round
+//! base weight plus the storage reads and writes it makes. Replace this file with the output
+//! of `./target/release/node-template benchmark --pallet=parameters --extrinsic=*` on
+//! reference hardware before relying on them.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for parameters.
+pub trait WeightInfo {
+	fn set_existential_deposit() -> Weight;
+	fn set_transaction_byte_fee() -> Weight;
+}
+
+/// Weights for parameters using the database weights of the runtime.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+	fn set_existential_deposit() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_transaction_byte_fee() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn set_existential_deposit() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_transaction_byte_fee() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+}
@@ -0,0 +1,174 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// A FRAME pallet which keeps runtime parameters in storage, so they can be tuned without
+/// a runtime upgrade.
+///
+/// Each parameter starts at its `Default*` value and can be changed by `SetOrigin` within
+/// the limits set by the runtime. Other pallets read the current values through the `Get`
+/// adapters at the bottom of this file.
+
+use codec::Codec;
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, dispatch, ensure, Parameter,
+	traits::{Get, EnsureOrigin},
+};
+use frame_system::{self as system};
+use sp_runtime::traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize, Member, Zero};
+use sp_std::marker::PhantomData;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+/// The pallet's configuration trait.
+pub trait Trait: system::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+
+	/// The balance type of the fees and deposits kept by this pallet.
+	type Balance: Parameter + Member + AtLeast32BitUnsigned + Codec + Default + Copy +
+		MaybeSerializeDeserialize;
+
+	/// The origin which may change the parameters.
+	type SetOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The existential deposit used until one is set.
+	type DefaultExistentialDeposit: Get<Self::Balance>;
+
+	/// The largest existential deposit which may be set.
+	type MaxExistentialDeposit: Get<Self::Balance>;
+
+	/// The fee per transaction byte used until one is set.
+	type DefaultTransactionByteFee: Get<Self::Balance>;
+
+	/// The largest fee per transaction byte which may be set.
+	type MaxTransactionByteFee: Get<Self::Balance>;
+
+	/// Weight information for the extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Parameters {
+		// The minimum balance an account must hold to exist.
+		ExistentialDeposit get(fn existential_deposit) config():
+			T::Balance = T::DefaultExistentialDeposit::get();
+
+		// The fee charged for each byte of a transaction.
+		TransactionByteFee get(fn transaction_byte_fee) config():
+			T::Balance = T::DefaultTransactionByteFee::get();
+	}
+	add_extra_genesis {
+		// The genesis values must be ones the setters would accept.
+		build(|config: &GenesisConfig<T>| {
+			assert!(
+				!config.existential_deposit.is_zero() &&
+					config.existential_deposit <= T::MaxExistentialDeposit::get(),
+				"`existential_deposit` is zero or above `MaxExistentialDeposit`",
+			);
+			assert!(
+				config.transaction_byte_fee <= T::MaxTransactionByteFee::get(),
+				"`transaction_byte_fee` is above `MaxTransactionByteFee`",
+			);
+		});
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where
+		Balance = <T as Trait>::Balance,
+	{
+		/// The existential deposit was changed.
+		ExistentialDepositSet(Balance),
+		/// The fee per transaction byte was changed.
+		TransactionByteFeeSet(Balance),
+	}
+);
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The value is zero, or above the limit set by the runtime
+		OutOfBounds,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// The largest existential deposit which may be set.
+		const MaxExistentialDeposit: T::Balance = T::MaxExistentialDeposit::get();
+
+		/// The largest fee per transaction byte which may be set.
+		const MaxTransactionByteFee: T::Balance = T::MaxTransactionByteFee::get();
+
+		/// Sets the minimum balance an account must hold to exist.
+		///
+		/// Accounts already below a raised deposit are only reaped when their balance
+		/// next changes.
+		///
+		/// The dispatch origin for this call must be `SetOrigin`.
+		#[weight = T::WeightInfo::set_existential_deposit()]
+		pub fn set_existential_deposit(origin, value: T::Balance) -> dispatch::DispatchResult {
+			T::SetOrigin::ensure_origin(origin)?;
+			ensure!(
+				!value.is_zero() && value <= T::MaxExistentialDeposit::get(),
+				Error::<T>::OutOfBounds
+			);
+
+			ExistentialDeposit::<T>::put(value);
+
+			Self::deposit_event(RawEvent::ExistentialDepositSet(value));
+			Ok(())
+		}
+
+		/// Sets the fee charged for each byte of a transaction.
+		///
+		/// The dispatch origin for this call must be `SetOrigin`.
+		#[weight = T::WeightInfo::set_transaction_byte_fee()]
+		pub fn set_transaction_byte_fee(origin, value: T::Balance) -> dispatch::DispatchResult {
+			T::SetOrigin::ensure_origin(origin)?;
+			ensure!(value <= T::MaxTransactionByteFee::get(), Error::<T>::OutOfBounds);
+
+			TransactionByteFee::<T>::put(value);
+
+			Self::deposit_event(RawEvent::TransactionByteFeeSet(value));
+			Ok(())
+		}
+	}
+}
+
+/// The current existential deposit, for `pallet_balances`.
+///
+/// Each call reads storage, which the weights of `pallet_balances` do not account for. The
+/// runtime adds one read to the base weight of every extrinsic, for the fee withdrawal;
+/// later reads in the same block are served from the overlay.
+pub struct ExistentialDepositOf<T>(PhantomData<T>);
+
+impl<T: Trait> Get<T::Balance> for ExistentialDepositOf<T> {
+	fn get() -> T::Balance {
+		Module::<T>::existential_deposit()
+	}
+}
+
+/// The current fee per transaction byte, for `pallet_transaction_payment`.
+///
+/// Each call reads storage. The runtime adds one read to the base weight of every
+/// extrinsic, for computing its fee.
+pub struct TransactionByteFeeOf<T>(PhantomData<T>);
+
+impl<T: Trait> Get<T::Balance> for TransactionByteFeeOf<T> {
+	fn get() -> T::Balance {
+		Module::<T>::transaction_byte_fee()
+	}
+}
@@ -9,19 +9,25 @@
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 use sp_std::prelude::*;
-use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
+use sp_core::{crypto::KeyTypeId, OpaqueMetadata, u32_trait::{_1, _2, _3, _4}};
 use sp_runtime::{
 	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, MultiSignature,
-	transaction_validity::{TransactionValidity, TransactionSource},
+	SaturatedConversion, FixedPointNumber, Perquintill, Percent, ModuleId, RuntimeDebug,
+	transaction_validity::{TransactionValidity, TransactionSource, TransactionPriority},
 };
 use sp_runtime::traits::{
-	BlakeTwo256, Block as BlockT, IdentityLookup, Verify, IdentifyAccount, NumberFor, Saturating,
+	BlakeTwo256, Block as BlockT, StaticLookup, Verify, IdentifyAccount, NumberFor, Saturating,
+	OpaqueKeys,
 };
+use codec::{Encode, Decode};
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use grandpa::{AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList};
 use grandpa::fg_primitives;
 use sp_version::RuntimeVersion;
+use session::historical as session_historical;
+use transaction_payment::{Multiplier, TargetedFeeAdjustment};
+use smallvec::smallvec;
 #[cfg(feature = "std")]
 use sp_version::NativeVersion;
 
@@ -32,17 +38,26 @@ pub use timestamp::Call as TimestampCall;
 pub use balances::Call as BalancesCall;
 pub use sp_runtime::{Permill, Perbill};
 pub use frame_support::{
-	construct_runtime, parameter_types, StorageValue,
-	traits::{KeyOwnerProofSystem, Randomness},
+	construct_runtime, debug, parameter_types, StorageValue,
+	traits::{KeyOwnerProofSystem, Randomness, Currency, Imbalance, OnUnbalanced, InstanceFilter},
 	weights::{
-		Weight, IdentityFee,
-		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
+		Weight, WeightToFeePolynomial, WeightToFeeCoefficient, WeightToFeeCoefficients,
+		constants::{BlockExecutionWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 	},
 };
 
 /// Importing a template pallet
 pub use template;
 
+/// Importing the validator set pallet
+pub use validator_set;
+
+/// Importing the parameters pallet
+pub use parameters;
+
+/// Importing the treasury pallet
+pub use treasury;
+
 /// An index to a block.
 pub type BlockNumber = u32;
 
@@ -63,6 +78,9 @@ pub type Balance = u128;
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
+/// The value the template pallet stores for each account.
+pub type TemplateValue = u32;
+
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
 
@@ -98,10 +116,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("node-template"),
 	impl_name: create_runtime_str!("node-template"),
 	authoring_version: 1,
-	spec_version: 1,
+	spec_version: 4,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 pub const MILLISECS_PER_BLOCK: u64 = 6000;
@@ -123,6 +141,8 @@ pub fn native_version() -> NativeVersion {
 }
 
 parameter_types! {
+	/// Mortal transactions in the pool are checked against the hashes kept here, so the
+	/// count only changes with a runtime upgrade that can account for them.
 	pub const BlockHashCount: BlockNumber = 2400;
 	/// We allow for 2 seconds of compute with a 6 second average block time.
 	pub const MaximumBlockWeight: Weight = 2 * WEIGHT_PER_SECOND;
@@ -132,15 +152,24 @@ parameter_types! {
 		.saturating_sub(Perbill::from_percent(10)) * MaximumBlockWeight::get();
 	pub const MaximumBlockLength: u32 = 5 * 1024 * 1024;
 	pub const Version: RuntimeVersion = VERSION;
+	/// Paying the fee of an extrinsic reads the byte fee and the existential deposit from
+	/// `Parameters`, which the benchmarked base weight does not include.
+	pub ExtrinsicBaseWeight: Weight = frame_support::weights::constants::ExtrinsicBaseWeight::get()
+		.saturating_add(RocksDbWeight::get().reads(2));
 }
 
 impl system::Trait for Runtime {
+	/// The basic call filter to use in dispatchable. Every call is allowed here: `Proxy` adds
+	/// the `InstanceFilter` of the proxy's `ProxyType` to the origin it dispatches with, and
+	/// `Utility` keeps that origin's filters for the calls in a batch.
+	type BaseCallFilter = ();
 	/// The identifier used to distinguish between accounts.
 	type AccountId = AccountId;
 	/// The aggregated dispatch type that is available for extrinsics.
 	type Call = Call;
 	/// The lookup mechanism to get account ID from whatever is passed in dispatchers.
-	type Lookup = IdentityLookup<AccountId>;
+	/// Accepts either an account ID or the account's index in `Indices`.
+	type Lookup = Indices;
 	/// The index type for storing how many extrinsics an account has signed.
 	type Index = Index;
 	/// The index type for blocks.
@@ -189,6 +218,69 @@ impl system::Trait for Runtime {
 	type AccountData = balances::AccountData<Balance>;
 }
 
+parameter_types! {
+	/// Reserved while an account holds an index.
+	pub const IndexDeposit: Balance = 10_000;
+}
+
+impl indices::Trait for Runtime {
+	/// The type for recording indexing into the account enumeration.
+	type AccountIndex = AccountIndex;
+	type Currency = Balances;
+	type Deposit = IndexDeposit;
+	type Event = Event;
+}
+
+parameter_types! {
+	pub const SessionPeriod: BlockNumber = HOURS;
+	pub const SessionOffset: BlockNumber = 0;
+	pub const DisabledValidatorsThreshold: Perbill = Perbill::from_percent(17);
+}
+
+impl session::Trait for Runtime {
+	type Event = Event;
+	/// Validators are identified by their account.
+	type ValidatorId = <Self as system::Trait>::AccountId;
+	type ValidatorIdOf = validator_set::ValidatorOf<Self>;
+	type ShouldEndSession = session::PeriodicSessions<SessionPeriod, SessionOffset>;
+	type NextSessionRotation = session::PeriodicSessions<SessionPeriod, SessionOffset>;
+	/// The validators of each session are taken from `ValidatorSet`.
+	type SessionManager = session_historical::NoteHistoricalRoot<Self, ValidatorSet>;
+	type SessionHandler = <opaque::SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
+	type Keys = opaque::SessionKeys;
+	type DisabledValidatorsThreshold = DisabledValidatorsThreshold;
+	type WeightInfo = ();
+}
+
+impl session_historical::Trait for Runtime {
+	/// Validators have no stake, so they are fully identified by their account.
+	type FullIdentification = AccountId;
+	type FullIdentificationOf = validator_set::ValidatorOf<Self>;
+}
+
+parameter_types! {
+	pub const MaxValidators: u32 = 100;
+}
+
+impl validator_set::Trait for Runtime {
+	type Event = Event;
+	type AddRemoveOrigin = EnsureRootOrHalfCouncil;
+	type MaxValidators = MaxValidators;
+	type WeightInfo = validator_set::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const UncleGenerations: BlockNumber = 0;
+}
+
+impl authorship::Trait for Runtime {
+	/// The author is the session validator at the index Aura records for the block.
+	type FindAuthor = session::FindAccountFromAuthorIndex<Self, Aura>;
+	type UncleGenerations = UncleGenerations;
+	type FilterUncle = ();
+	type EventHandler = ();
+}
+
 impl aura::Trait for Runtime {
 	type AuthorityId = AuraId;
 }
@@ -197,7 +289,7 @@ impl grandpa::Trait for Runtime {
 	type Event = Event;
 	type Call = Call;
 
-	type KeyOwnerProofSystem = ();
+	type KeyOwnerProofSystem = Historical;
 
 	type KeyOwnerProof =
 		<Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(KeyTypeId, GrandpaId)>>::Proof;
@@ -207,10 +299,23 @@ impl grandpa::Trait for Runtime {
 		GrandpaId,
 	)>>::IdentificationTuple;
 
-	type HandleEquivocation = ();
+	type HandleEquivocation = grandpa::EquivocationHandler<Self::KeyOwnerIdentification, Offences>;
 }
 
 parameter_types! {
+	pub OffencesWeightSoftLimit: Weight = Perbill::from_percent(60) * MaximumBlockWeight::get();
+}
+
+impl offences::Trait for Runtime {
+	type Event = Event;
+	type IdentificationTuple = session_historical::IdentificationTuple<Self>;
+	/// Offences are only recorded. There is no stake to slash.
+	type OnOffenceHandler = ();
+	type WeightSoftLimit = OffencesWeightSoftLimit;
+}
+
+parameter_types! {
+	/// Half the slot duration, which Aura derives from this. Nodes read it once at startup.
 	pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
 }
 
@@ -222,7 +327,42 @@ impl timestamp::Trait for Runtime {
 }
 
 parameter_types! {
-	pub const ExistentialDeposit: u128 = 500;
+	pub const TreasuryModuleId: ModuleId = ModuleId(*b"py/trsry");
+	/// The share of transaction fees and tips paid to the treasury. The rest goes to the
+	/// block author.
+	pub const TreasuryFeeShare: Percent = Percent::from_percent(20);
+}
+
+/// Fees, dust and slashed deposits are paid into the treasury, from which the council can
+/// spend them.
+impl treasury::Trait for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type ModuleId = TreasuryModuleId;
+	type SpendOrigin = EnsureRootOrHalfCouncil;
+	type WeightInfo = treasury::weights::SubstrateWeight<Runtime>;
+}
+
+type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
+
+/// Pays whatever it is given to the author of the current block.
+pub struct ToAuthor;
+impl OnUnbalanced<NegativeImbalance> for ToAuthor {
+	fn on_nonzero_unbalanced(amount: NegativeImbalance) {
+		Balances::resolve_creating(&Authorship::author(), amount);
+	}
+}
+
+/// Splits transaction fees and tips between the treasury and the block author according
+/// to `TreasuryFeeShare`.
+pub struct DealWithFees;
+impl OnUnbalanced<NegativeImbalance> for DealWithFees {
+	fn on_nonzero_unbalanced(amount: NegativeImbalance) {
+		let to_treasury = TreasuryFeeShare::get() * amount.peek();
+		let (treasury, author) = amount.split(to_treasury);
+		Treasury::on_unbalanced(treasury);
+		ToAuthor::on_unbalanced(author);
+	}
 }
 
 impl balances::Trait for Runtime {
@@ -230,31 +370,364 @@ impl balances::Trait for Runtime {
 	type Balance = Balance;
 	/// The ubiquitous event type.
 	type Event = Event;
-	type DustRemoval = ();
-	type ExistentialDeposit = ExistentialDeposit;
+	/// Dust from reaped accounts goes to the treasury.
+	type DustRemoval = Treasury;
+	type ExistentialDeposit = parameters::ExistentialDepositOf<Runtime>;
 	type AccountStore = System;
 }
 
 parameter_types! {
-	pub const TransactionByteFee: Balance = 1;
+	/// The integer part of the fee charged per unit of weight.
+	pub const WeightFeeInteger: Balance = 1;
+	/// The fractional part of the fee charged per unit of weight.
+	pub const WeightFeeFraction: Perbill = Perbill::from_percent(0);
+	/// The portion of the weight available to normal transactions (`AvailableBlockRatio` of
+	/// `MaximumBlockWeight`) that blocks should use. Fees rise above it and fall below it.
+	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+	/// How quickly the fee multiplier reacts to blocks being above or below the target.
+	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(3, 100_000);
+	/// The fee multiplier never drops below this, so fees cannot fall to zero.
+	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 1_000_000_000u128);
+}
+
+/// Converts a weight into a fee using a degree one polynomial: every unit of weight costs
+/// `WeightFeeInteger + WeightFeeFraction`.
+pub struct WeightToFee;
+impl WeightToFeePolynomial for WeightToFee {
+	type Balance = Balance;
+	fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+		smallvec![WeightToFeeCoefficient {
+			coeff_integer: WeightFeeInteger::get(),
+			coeff_frac: WeightFeeFraction::get(),
+			negative: false,
+			degree: 1,
+		}]
+	}
 }
 
 impl transaction_payment::Trait for Runtime {
 	type Currency = balances::Module<Runtime>;
-	type OnTransactionPayment = ();
-	type TransactionByteFee = TransactionByteFee;
-	type WeightToFee = IdentityFee<Balance>;
-	type FeeMultiplierUpdate = ();
+	type OnTransactionPayment = DealWithFees;
+	type TransactionByteFee = parameters::TransactionByteFeeOf<Runtime>;
+	type WeightToFee = WeightToFee;
+	type FeeMultiplierUpdate =
+		TargetedFeeAdjustment<Self, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
 }
 
+/// Sudo is kept while privileged actions move to governance. Every privileged origin already
+/// accepts the council, see `EnsureRootOrHalfCouncil`, and root is reached through referenda.
+///
+/// The handover takes two steps:
+///
+/// 1. With this runtime (`spec_version` 4), the sudo key seats the first council with
+///    `Sudo::sudo(Council::set_members(..))`. It should make no other calls from then on.
+/// 2. Once the council has carried a referendum to enactment, it proposes the upgrade to
+///    `spec_version` 5 through `Democracy`. That runtime removes `Sudo` from
+///    `construct_runtime!` and kills its `Key` in `on_runtime_upgrade`. Removing a module
+///    shifts the indices of the modules after it, so it also bumps `transaction_version`.
 impl sudo::Trait for Runtime {
 	type Event = Event;
 	type Call = Call;
 }
 
+parameter_types! {
+	pub const DefaultExistentialDeposit: Balance = 500;
+	pub const MaxExistentialDeposit: Balance = 1_000_000;
+	pub const DefaultTransactionByteFee: Balance = 1;
+	pub const MaxTransactionByteFee: Balance = 1_000;
+}
+
+impl parameters::Trait for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type SetOrigin = EnsureRootOrHalfCouncil;
+	type DefaultExistentialDeposit = DefaultExistentialDeposit;
+	type MaxExistentialDeposit = MaxExistentialDeposit;
+	type DefaultTransactionByteFee = DefaultTransactionByteFee;
+	type MaxTransactionByteFee = MaxTransactionByteFee;
+	type WeightInfo = parameters::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const CouncilMotionDuration: BlockNumber = 5 * MINUTES;
+	pub const CouncilMaxProposals: u32 = 100;
+}
+
+type CouncilCollective = collective::Instance1;
+impl collective::Trait<CouncilCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = CouncilMotionDuration;
+	type MaxProposals = CouncilMaxProposals;
+}
+
+/// Root, which is reached through referenda, or more than half of the council.
+pub type EnsureRootOrHalfCouncil = system::EnsureOneOf<
+	AccountId,
+	system::EnsureRoot<AccountId>,
+	collective::EnsureProportionMoreThan<_1, _2, AccountId, CouncilCollective>,
+>;
+
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * MaximumBlockWeight::get();
+}
+
+impl scheduler::Trait for Runtime {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = MaximumSchedulerWeight;
+}
+
+// The periods are kept short so that proposals can be tried out on test chains.
+parameter_types! {
+	pub const LaunchPeriod: BlockNumber = 5 * MINUTES;
+	pub const VotingPeriod: BlockNumber = 5 * MINUTES;
+	pub const FastTrackVotingPeriod: BlockNumber = MINUTES;
+	pub const EnactmentPeriod: BlockNumber = 2 * MINUTES;
+	pub const CooloffPeriod: BlockNumber = 5 * MINUTES;
+	/// Instant referenda would let the council enact proposals without a vote.
+	pub const InstantAllowed: bool = false;
+	pub const MinimumDeposit: Balance = 100_000;
+	pub const PreimageByteDeposit: Balance = 100;
+	pub const MaxVotes: u32 = 100;
+}
+
+impl democracy::Trait for Runtime {
+	type Proposal = Call;
+	type Event = Event;
+	type Currency = Balances;
+	type EnactmentPeriod = EnactmentPeriod;
+	type LaunchPeriod = LaunchPeriod;
+	type VotingPeriod = VotingPeriod;
+	type MinimumDeposit = MinimumDeposit;
+	/// A simple majority of the council can decide what their next motion is.
+	type ExternalOrigin = collective::EnsureProportionAtLeast<_1, _2, AccountId, CouncilCollective>;
+	/// A super-majority can have the next scheduled referendum be a straight majority-carries vote.
+	type ExternalMajorityOrigin =
+		collective::EnsureProportionAtLeast<_3, _4, AccountId, CouncilCollective>;
+	/// A unanimous council can have the next scheduled referendum be a straight default-carries
+	/// (NTB) vote.
+	type ExternalDefaultOrigin =
+		collective::EnsureProportionAtLeast<_1, _1, AccountId, CouncilCollective>;
+	/// Two thirds of the council can have an external referendum voted on with a shorter period.
+	type FastTrackOrigin = collective::EnsureProportionAtLeast<_2, _3, AccountId, CouncilCollective>;
+	/// Unused while `InstantAllowed` is false.
+	type InstantOrigin = collective::EnsureProportionAtLeast<_1, _1, AccountId, CouncilCollective>;
+	type InstantAllowed = InstantAllowed;
+	type FastTrackVotingPeriod = FastTrackVotingPeriod;
+	/// Two thirds of the council can cancel a referendum.
+	type CancellationOrigin =
+		collective::EnsureProportionAtLeast<_2, _3, AccountId, CouncilCollective>;
+	/// Any council member can veto a coming council proposal, but only once for a given proposal.
+	type VetoOrigin = collective::EnsureMember<AccountId, CouncilCollective>;
+	type CooloffPeriod = CooloffPeriod;
+	type PreimageByteDeposit = PreimageByteDeposit;
+	type OperationalPreimageOrigin = collective::EnsureMember<AccountId, CouncilCollective>;
+	/// Slashed deposits go to the treasury.
+	type Slash = Treasury;
+	type Scheduler = Scheduler;
+	type PalletsOrigin = OriginCaller;
+	type MaxVotes = MaxVotes;
+}
+
+impl utility::Trait for Runtime {
+	type Event = Event;
+	type Call = Call;
+}
+
+parameter_types! {
+	/// Reserved while a multisig operation is open, for the storage of the operation.
+	pub const MultisigDepositBase: Balance = 10_000;
+	/// Reserved on top of `MultisigDepositBase` for each unit of the threshold.
+	pub const MultisigDepositFactor: Balance = 3_200;
+	pub const MaxSignatories: u16 = 100;
+}
+
+impl multisig::Trait for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type DepositBase = MultisigDepositBase;
+	type DepositFactor = MultisigDepositFactor;
+	type MaxSignatories = MaxSignatories;
+}
+
+/// The kinds of proxy an account can add, each allowed a subset of the runtime's calls.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug)]
+pub enum ProxyType {
+	/// Any call.
+	Any,
+	/// Any call which cannot move balances or account indices, or act as the sudo key.
+	NonTransfer,
+	/// Calls to either instance of the template pallet, for hot keys that store values.
+	TemplateOnly,
+	/// Calls to the council and democracy.
+	Governance,
+}
+
+impl Default for ProxyType {
+	fn default() -> Self {
+		Self::Any
+	}
+}
+
+impl InstanceFilter<Call> for ProxyType {
+	fn filter(&self, c: &Call) -> bool {
+		match self {
+			ProxyType::Any => true,
+			ProxyType::NonTransfer => !matches!(
+				c,
+				Call::Balances(..) | Call::Indices(indices::Call::transfer(..)) | Call::Sudo(..)
+			),
+			// `Utility` dispatches its calls with the proxy's filter, so batches are allowed.
+			ProxyType::TemplateOnly => matches!(
+				c,
+				Call::TemplateModule(..) | Call::SecondTemplateModule(..) | Call::Utility(..)
+			),
+			ProxyType::Governance => matches!(
+				c,
+				Call::Council(..) | Call::Democracy(..) | Call::Utility(..)
+			),
+		}
+	}
+
+	fn is_superset(&self, o: &Self) -> bool {
+		match (self, o) {
+			(x, y) if x == y => true,
+			(ProxyType::Any, _) => true,
+			(_, ProxyType::Any) => false,
+			(ProxyType::NonTransfer, _) => true,
+			_ => false,
+		}
+	}
+}
+
+parameter_types! {
+	/// Reserved for having any proxies at all.
+	pub const ProxyDepositBase: Balance = 10_000;
+	/// Reserved on top of `ProxyDepositBase` for each proxy.
+	pub const ProxyDepositFactor: Balance = 3_300;
+	pub const MaxProxies: u16 = 32;
+}
+
+impl proxy::Trait for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ProxyDepositBase;
+	type ProxyDepositFactor = ProxyDepositFactor;
+	type MaxProxies = MaxProxies;
+}
+
+parameter_types! {
+	pub const HistoryDepth: u32 = 10;
+	pub const ValueLifetime: BlockNumber = 7 * DAYS;
+	pub const TemplateUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+	pub const TemplateUnsignedInterval: BlockNumber = 5;
+	pub const TemplateStep: TemplateValue = 1;
+	pub const TemplateValueDeposit: Balance = 50_000;
+}
+
+parameter_types! {
+	/// The account which held the sudo key when the single template value became per-account,
+	/// and which the value is migrated to: the development key `//Alice`. It is fixed, so the
+	/// migration does not depend on `Sudo` still being in the runtime.
+	pub LegacyValueOwner: AccountId = AccountId::from([
+		0xd4, 0x35, 0x93, 0xc7, 0x15, 0xfd, 0xd3, 0x1c, 0x61, 0x14, 0x1a, 0xbd, 0x04, 0xa9, 0x9f, 0xd6,
+		0x82, 0x2c, 0x85, 0x58, 0x85, 0x4c, 0xcd, 0xe3, 0x9a, 0x56, 0x84, 0xe7, 0xa5, 0x6d, 0xa2, 0x7d,
+	]);
+}
+
 /// Used for the module template in `./template.rs`
 impl template::Trait for Runtime {
+	type AuthorityId = template::crypto::TestAuthId;
+	type Event = Event;
+	type Value = TemplateValue;
+	type Step = TemplateStep;
+	type Currency = Balances;
+	type ValueDeposit = TemplateValueDeposit;
+	type HistoryDepth = HistoryDepth;
+	type ValueLifetime = ValueLifetime;
+	type AdminOrigin = EnsureRootOrHalfCouncil;
+	type UnsignedPriority = TemplateUnsignedPriority;
+	type UnsignedInterval = TemplateUnsignedInterval;
+	type LegacyValueOwner = LegacyValueOwner;
+	type WeightInfo = template::weights::SubstrateWeight<Runtime>;
+}
+
+/// A second, independent instance of the template pallet, with its own storage and events.
+impl template::Trait<template::Instance1> for Runtime {
+	type AuthorityId = template::crypto::TestAuthId;
 	type Event = Event;
+	type Value = TemplateValue;
+	type Step = TemplateStep;
+	type Currency = Balances;
+	type ValueDeposit = TemplateValueDeposit;
+	type HistoryDepth = HistoryDepth;
+	type ValueLifetime = ValueLifetime;
+	type AdminOrigin = EnsureRootOrHalfCouncil;
+	type UnsignedPriority = TemplateUnsignedPriority;
+	type UnsignedInterval = TemplateUnsignedInterval;
+	/// This instance was added after version 1, so it has no legacy value to migrate.
+	type LegacyValueOwner = ();
+	type WeightInfo = template::weights::SubstrateWeight<Runtime>;
+}
+
+impl<LocalCall> system::offchain::CreateSignedTransaction<LocalCall> for Runtime where
+	Call: From<LocalCall>,
+{
+	fn create_transaction<C: system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		public: <Signature as Verify>::Signer,
+		account: AccountId,
+		nonce: Index,
+	) -> Option<(Call, <UncheckedExtrinsic as sp_runtime::traits::Extrinsic>::SignaturePayload)> {
+		// take the biggest period possible.
+		let period = BlockHashCount::get()
+			.checked_next_power_of_two()
+			.map(|c| c / 2)
+			.unwrap_or(2) as u64;
+		let current_block = System::block_number()
+			.saturated_into::<u64>()
+			// The `System::block_number` is initialized with `n+1`,
+			// so the actual block number is `n`.
+			.saturating_sub(1);
+		let tip = 0;
+		let extra: SignedExtra = (
+			system::CheckSpecVersion::<Runtime>::new(),
+			system::CheckTxVersion::<Runtime>::new(),
+			system::CheckGenesis::<Runtime>::new(),
+			system::CheckEra::<Runtime>::from(generic::Era::mortal(period, current_block)),
+			system::CheckNonce::<Runtime>::from(nonce),
+			system::CheckWeight::<Runtime>::new(),
+			transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
+		);
+		let raw_payload = SignedPayload::new(call, extra)
+			.map_err(|e| {
+				debug::warn!("Unable to create signed payload: {:?}", e);
+			})
+			.ok()?;
+		let signature = raw_payload.using_encoded(|payload| C::sign(payload, public))?;
+		let (call, extra, _) = raw_payload.deconstruct();
+		let address = Indices::unlookup(account);
+		Some((call, (address, signature, extra)))
+	}
+}
+
+impl system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<C> system::offchain::SendTransactionTypes<C> for Runtime where
+	Call: From<C>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = UncheckedExtrinsic;
 }
 
 construct_runtime!(
@@ -266,18 +739,37 @@ construct_runtime!(
 		System: system::{Module, Call, Config, Storage, Event<T>},
 		RandomnessCollectiveFlip: randomness_collective_flip::{Module, Call, Storage},
 		Timestamp: timestamp::{Module, Call, Storage, Inherent},
+		// The Aura and GRANDPA authorities are rotated by `Session`, so their genesis
+		// authorities must be left empty and set through the session keys instead.
 		Aura: aura::{Module, Config<T>, Inherent(Timestamp)},
-		Grandpa: grandpa::{Module, Call, Storage, Config, Event},
+		Grandpa: grandpa::{Module, Call, Storage, Config, Event, ValidateUnsigned},
 		Balances: balances::{Module, Call, Storage, Config<T>, Event<T>},
 		TransactionPayment: transaction_payment::{Module, Storage},
 		Sudo: sudo::{Module, Call, Config<T>, Storage, Event<T>},
 		// Used for the module template in `./template.rs`
-		TemplateModule: template::{Module, Call, Storage, Event<T>},
+		TemplateModule: template::{Module, Call, Config<T>, Storage, Event<T>, ValidateUnsigned},
+		SecondTemplateModule: template::<Instance1>::{Module, Call, Config<T>, Storage, Event<T>, ValidateUnsigned},
+		// Appended, so the indices of the modules above and their calls stay the same.
+		Council: collective::<Instance1>::{Module, Call, Storage, Origin<T>, Event<T>, Config<T>},
+		Scheduler: scheduler::{Module, Call, Storage, Event<T>},
+		Democracy: democracy::{Module, Call, Storage, Config, Event<T>},
+		Parameters: parameters::{Module, Call, Storage, Event<T>, Config<T>},
+		Utility: utility::{Module, Call, Event},
+		Multisig: multisig::{Module, Call, Storage, Event<T>},
+		Proxy: proxy::{Module, Call, Storage, Event<T>},
+		Indices: indices::{Module, Call, Storage, Event<T>, Config<T>},
+		// `ValidatorSet` must come before `Session`, whose genesis asks it for the validators.
+		ValidatorSet: validator_set::{Module, Call, Storage, Event<T>, Config<T>},
+		Session: session::{Module, Call, Storage, Event, Config<T>},
+		Historical: session_historical::{Module},
+		Offences: offences::{Module, Call, Storage, Event},
+		Authorship: authorship::{Module, Call, Storage},
+		Treasury: treasury::{Module, Call, Storage, Config, Event<T>},
 	}
 );
 
 /// The address format for describing accounts.
-pub type Address = AccountId;
+pub type Address = <Indices as StaticLookup>::Source;
 /// Block header type as expected by this runtime.
 pub type Header = generic::Header<BlockNumber, BlakeTwo256>;
 /// Block type as expected by this runtime.
@@ -298,11 +790,16 @@ pub type SignedExtra = (
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
+/// The payload being signed in transactions.
+pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
 /// Extrinsic type that has already been checked.
 pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, Call, SignedExtra>;
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<Runtime, Block, system::ChainContext<Runtime>, Runtime, AllModules>;
 
+#[cfg(test)]
+mod tests;
+
 impl_runtime_apis! {
 	impl sp_api::Core<Block> for Runtime {
 		fn version() -> RuntimeVersion {
@@ -386,29 +883,94 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl template_runtime_api::TemplateApi<Block, AccountId, TemplateValue> for Runtime {
+		fn get_something(account: AccountId) -> Option<TemplateValue> {
+			TemplateModule::something(account)
+		}
+
+		fn get_something_history(account: AccountId) -> Vec<TemplateValue> {
+			TemplateModule::something_history(account)
+		}
+
+		fn get_statistics() -> template::SomethingStatistics<TemplateValue> {
+			TemplateModule::statistics()
+		}
+	}
+
+	impl frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Index> for Runtime {
+		fn account_nonce(account: AccountId) -> Index {
+			System::account_nonce(account)
+		}
+	}
+
+	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
+		fn query_info(
+			uxt: <Block as BlockT>::Extrinsic,
+			len: u32,
+		) -> pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo<Balance> {
+			TransactionPayment::query_info(uxt, len)
+		}
+	}
+
 	impl fg_primitives::GrandpaApi<Block> for Runtime {
 		fn grandpa_authorities() -> GrandpaAuthorityList {
 			Grandpa::grandpa_authorities()
 		}
 
-		fn submit_report_equivocation_extrinsic(
-			_equivocation_proof: fg_primitives::EquivocationProof<
+		fn submit_report_equivocation_unsigned_extrinsic(
+			equivocation_proof: fg_primitives::EquivocationProof<
 				<Block as BlockT>::Hash,
 				NumberFor<Block>,
 			>,
-			_key_owner_proof: fg_primitives::OpaqueKeyOwnershipProof,
+			key_owner_proof: fg_primitives::OpaqueKeyOwnershipProof,
 		) -> Option<()> {
-			None
+			let key_owner_proof = key_owner_proof.decode()?;
+
+			Grandpa::submit_unsigned_equivocation_report(
+				equivocation_proof,
+				key_owner_proof,
+			)
 		}
 
 		fn generate_key_ownership_proof(
 			_set_id: fg_primitives::SetId,
-			_authority_id: GrandpaId,
+			authority_id: GrandpaId,
 		) -> Option<fg_primitives::OpaqueKeyOwnershipProof> {
-			// NOTE: this is the only implementation possible since we've
-			// defined our key owner proof type as a bottom type (i.e. a type
-			// with no values).
-			None
+			Historical::prove((fg_primitives::KEY_TYPE, authority_id))
+				.map(|p| p.encode())
+				.map(fg_primitives::OpaqueKeyOwnershipProof::new)
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	impl frame_benchmarking::Benchmark<Block> for Runtime {
+		fn dispatch_benchmark(
+			pallet: Vec<u8>,
+			benchmark: Vec<u8>,
+			lowest_range_values: Vec<u32>,
+			highest_range_values: Vec<u32>,
+			steps: Vec<u32>,
+			repeat: u32,
+		) -> Result<Vec<frame_benchmarking::BenchmarkBatch>, sp_runtime::RuntimeString> {
+			use frame_benchmarking::{Benchmarking, BenchmarkBatch, add_benchmark};
+			// Trying to add benchmarks directly to the System Module caused cyclic dependency issues.
+			// To get around that, we separated the System benchmarks into its own crate.
+			use frame_system_benchmarking::Module as SystemBench;
+			impl frame_system_benchmarking::Trait for Runtime {}
+
+			let mut batches = Vec::<BenchmarkBatch>::new();
+			let params = (&pallet, &benchmark, &lowest_range_values, &highest_range_values, &steps, repeat);
+
+			add_benchmark!(params, batches, b"system", SystemBench::<Runtime>);
+			add_benchmark!(params, batches, b"balances", Balances);
+			add_benchmark!(params, batches, b"timestamp", Timestamp);
+			add_benchmark!(params, batches, b"template", TemplateModule);
+			add_benchmark!(params, batches, b"validator-set", ValidatorSet);
+			add_benchmark!(params, batches, b"parameters", Parameters);
+			add_benchmark!(params, batches, b"treasury", Treasury);
+
+			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
+			Ok(batches)
 		}
 	}
 }
@@ -0,0 +1,650 @@
+use super::*;
+use frame_support::traits::{ExistenceRequirement, OnInitialize, WithdrawReason};
+use frame_support::{assert_ok, weights::GetDispatchInfo};
+use frame_system::InitKind;
+use frame_system_rpc_runtime_api::runtime_decl_for_AccountNonceApi::AccountNonceApi;
+use pallet_transaction_payment_rpc_runtime_api::runtime_decl_for_TransactionPaymentApi::TransactionPaymentApi;
+use template_runtime_api::runtime_decl_for_TemplateApi::TemplateApi;
+use grandpa::fg_primitives::{self, runtime_decl_for_GrandpaApi::GrandpaApi};
+use sp_core::Pair;
+use sp_consensus_aura::AURA_ENGINE_ID;
+use sp_runtime::{Digest, DigestItem, traits::{Convert, Hash as HashT}};
+
+type FeeMultiplierUpdate = <Runtime as transaction_payment::Trait>::FeeMultiplierUpdate;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	system::GenesisConfig::default().build_storage::<Runtime>().unwrap().into()
+}
+
+fn account(seed: u8) -> AccountId {
+	AccountId::from([seed; 32])
+}
+
+fn grandpa_pair(seed: u8) -> sp_core::ed25519::Pair {
+	sp_core::ed25519::Pair::from_seed(&[seed; 32])
+}
+
+fn session_keys(seed: u8) -> opaque::SessionKeys {
+	opaque::SessionKeys {
+		aura: sp_core::sr25519::Public::from_raw([seed; 32]).into(),
+		// A real key, so that tests can sign GRANDPA votes with it.
+		grandpa: grandpa_pair(seed).public().into(),
+	}
+}
+
+/// Builds a genesis where `author` is the only validator and `balances` are endowed, then
+/// starts block 1 with an Aura digest so that `author` is found as its author.
+pub fn new_test_ext_with_author(
+	author: AccountId,
+	balances: Vec<(AccountId, Balance)>,
+) -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+	balances::GenesisConfig::<Runtime> { balances }.assimilate_storage(&mut t).unwrap();
+	validator_set::GenesisConfig::<Runtime> {
+		validators: vec![author.clone()],
+	}.assimilate_storage(&mut t).unwrap();
+	session::GenesisConfig::<Runtime> {
+		keys: vec![(author.clone(), author, session_keys(1))],
+	}.assimilate_storage(&mut t).unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		let digest = Digest { logs: vec![DigestItem::PreRuntime(AURA_ENGINE_ID, 0u64.encode())] };
+		System::initialize(&1, &Default::default(), &Default::default(), &digest, InitKind::Full);
+	});
+	ext
+}
+
+/// The weight a block can use for normal transactions.
+fn max_normal() -> Weight {
+	AvailableBlockRatio::get() * MaximumBlockWeight::get()
+}
+
+/// The weight at which the fee multiplier stays the same.
+fn target() -> Weight {
+	TargetBlockFullness::get() * max_normal()
+}
+
+/// Runs `blocks` blocks that each use `weight`, returning the multiplier after each one.
+fn multipliers_after(start: Multiplier, weight: Weight, blocks: usize) -> Vec<Multiplier> {
+	let mut multiplier = start;
+	(0..blocks).map(|_| {
+		System::set_block_limits(weight, 0);
+		multiplier = FeeMultiplierUpdate::convert(multiplier);
+		multiplier
+	}).collect()
+}
+
+#[test]
+fn multiplier_rises_on_full_blocks() {
+	new_test_ext().execute_with(|| {
+		let start = Multiplier::saturating_from_integer(1);
+		let multipliers = multipliers_after(start, max_normal(), 20);
+
+		assert!(multipliers[0] > start);
+		assert!(multipliers.windows(2).all(|w| w[1] > w[0]));
+	});
+}
+
+#[test]
+fn multiplier_falls_on_empty_blocks_down_to_the_minimum() {
+	new_test_ext().execute_with(|| {
+		let start = Multiplier::saturating_from_integer(1);
+		let multipliers = multipliers_after(start, 0, 20);
+
+		assert!(multipliers[0] < start);
+		assert!(multipliers.windows(2).all(|w| w[1] < w[0]));
+
+		let at_minimum = multipliers_after(MinimumMultiplier::get(), 0, 20);
+		assert!(at_minimum.iter().all(|m| *m == MinimumMultiplier::get()));
+	});
+}
+
+#[test]
+fn multiplier_is_stable_at_target_fullness() {
+	new_test_ext().execute_with(|| {
+		let start = Multiplier::saturating_from_integer(2);
+		let multipliers = multipliers_after(start, target(), 20);
+
+		assert!(multipliers.iter().all(|m| *m == start));
+	});
+}
+
+#[test]
+fn multiplier_recovers_after_a_run_of_full_blocks() {
+	new_test_ext().execute_with(|| {
+		let start = Multiplier::saturating_from_integer(1);
+		let peak = *multipliers_after(start, max_normal(), 50).last().unwrap();
+		let after = *multipliers_after(peak, 0, 50).last().unwrap();
+
+		assert!(peak > start);
+		assert!(after < peak);
+	});
+}
+
+#[test]
+fn weight_to_fee_matches_parameters() {
+	let weight: Weight = 1_000_000;
+	let expected = WeightFeeInteger::get() * weight as Balance
+		+ WeightFeeFraction::get() * weight as Balance;
+	assert_eq!(WeightToFee::calc(&weight), expected);
+}
+
+#[test]
+fn fees_are_split_between_treasury_and_author() {
+	let (author, payer) = (account(1), account(2));
+	let endowed = vec![(payer.clone(), 100_000), (Treasury::account_id(), DefaultExistentialDeposit::get())];
+	new_test_ext_with_author(author.clone(), endowed).execute_with(|| {
+		assert_eq!(Authorship::author(), author);
+		let issuance = Balances::total_issuance();
+
+		let fee = Balances::withdraw(
+			&payer,
+			10_000,
+			WithdrawReason::TransactionPayment.into(),
+			ExistenceRequirement::KeepAlive,
+		).unwrap();
+		DealWithFees::on_unbalanced(fee);
+
+		// `TreasuryFeeShare` is 20%.
+		assert_eq!(Treasury::pot(), 2_000);
+		assert_eq!(Balances::free_balance(&author), 8_000);
+		assert_eq!(Balances::free_balance(&payer), 90_000);
+		assert_eq!(Balances::total_issuance(), issuance);
+	});
+}
+
+#[test]
+fn dust_goes_to_the_treasury() {
+	let (author, payer, dest) = (account(1), account(2), account(3));
+	let endowed = vec![(payer.clone(), 10_000), (Treasury::account_id(), DefaultExistentialDeposit::get())];
+	new_test_ext_with_author(author, endowed).execute_with(|| {
+		let issuance = Balances::total_issuance();
+
+		// Leaves 100 behind, which is below the existential deposit, so `payer` is reaped.
+		assert_ok!(Balances::transfer(Origin::signed(payer.clone()), dest.clone().into(), 9_900));
+
+		assert_eq!(Balances::free_balance(&payer), 0);
+		assert_eq!(Balances::free_balance(&dest), 9_900);
+		assert_eq!(Treasury::pot(), 100);
+		assert_eq!(Balances::total_issuance(), issuance);
+	});
+}
+
+/// A signed extrinsic from `signer`. The signature is not valid, which fee estimation ignores.
+fn signed_extrinsic(signer: AccountId, call: Call) -> UncheckedExtrinsic {
+	let extra: SignedExtra = (
+		system::CheckSpecVersion::<Runtime>::new(),
+		system::CheckTxVersion::<Runtime>::new(),
+		system::CheckGenesis::<Runtime>::new(),
+		system::CheckEra::<Runtime>::from(generic::Era::Immortal),
+		system::CheckNonce::<Runtime>::from(0),
+		system::CheckWeight::<Runtime>::new(),
+		transaction_payment::ChargeTransactionPayment::<Runtime>::from(0),
+	);
+	let signature = sp_core::sr25519::Signature::from_raw([0; 64]).into();
+	UncheckedExtrinsic::new_signed(call, signer.into(), signature, extra)
+}
+
+#[test]
+fn query_info_quotes_the_fee_charged() {
+	new_test_ext().execute_with(|| {
+		let xt = signed_extrinsic(account(1), Call::TemplateModule(template::Call::do_something(42)));
+		let len = xt.encode().len() as u32;
+
+		let info = Runtime::query_info(xt.clone(), len);
+
+		let dispatch_info = xt.get_dispatch_info();
+		assert_eq!(info.weight, dispatch_info.weight);
+		assert_eq!(info.class, dispatch_info.class);
+		assert_eq!(info.partial_fee, TransactionPayment::compute_fee(len, &dispatch_info, 0));
+		assert!(info.partial_fee > 0);
+	});
+}
+
+#[test]
+fn query_info_charges_for_length() {
+	new_test_ext().execute_with(|| {
+		let xt = signed_extrinsic(account(1), Call::TemplateModule(template::Call::do_something(42)));
+
+		let short = Runtime::query_info(xt.clone(), 100);
+		let long = Runtime::query_info(xt, 200);
+
+		assert_eq!(long.partial_fee - short.partial_fee, 100 * Parameters::transaction_byte_fee());
+	});
+}
+
+#[test]
+fn account_nonce_reads_system_nonce() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Runtime::account_nonce(account(1)), 0);
+
+		System::inc_account_nonce(&account(1));
+		System::inc_account_nonce(&account(1));
+
+		assert_eq!(Runtime::account_nonce(account(1)), 2);
+		assert_eq!(Runtime::account_nonce(account(2)), 0);
+	});
+}
+
+#[test]
+fn template_api_reads_pallet_state() {
+	let balances = vec![(account(1), 1_000_000), (account(2), 1_000_000)];
+	new_test_ext_with_author(account(0), balances).execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(account(1)), 1));
+		assert_ok!(TemplateModule::do_something(Origin::signed(account(1)), 2));
+		assert_ok!(TemplateModule::do_something(Origin::signed(account(2)), 5));
+
+		assert_eq!(Runtime::get_something(account(1)), Some(2));
+		assert_eq!(Runtime::get_something(account(3)), None);
+		assert_eq!(Runtime::get_something_history(account(1)), vec![1]);
+		assert_eq!(Runtime::get_statistics(), template::SomethingStatistics {
+			accounts: 2,
+			total: 7,
+			min: Some(2),
+			max: Some(5),
+		});
+	});
+}
+
+/// Builds a genesis with the given council.
+fn new_test_ext_with_council(members: Vec<AccountId>) -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+	collective::GenesisConfig::<Runtime, CouncilCollective> {
+		members,
+		phantom: Default::default(),
+	}.assimilate_storage(&mut t).unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Proposes `call` to the council with the given threshold, returning its hash and length.
+fn propose(proposer: AccountId, threshold: u32, call: Call) -> (Hash, u32) {
+	let hash = BlakeTwo256::hash_of(&call);
+	let len = call.encode().len() as u32;
+	assert_ok!(Council::propose(Origin::signed(proposer), threshold, Box::new(call), len));
+	(hash, len)
+}
+
+#[test]
+fn council_motion_pauses_the_template_pallet() {
+	new_test_ext_with_council(vec![account(1), account(2), account(3)]).execute_with(|| {
+		let call = Call::TemplateModule(template::Call::set_paused(true));
+		let (hash, len) = propose(account(1), 2, call);
+
+		// The proposer's vote alone is not enough.
+		assert!(!TemplateModule::paused());
+
+		assert_ok!(Council::vote(Origin::signed(account(2)), hash, 0, true));
+		assert_ok!(Council::close(Origin::signed(account(3)), hash, 0, Weight::max_value(), len));
+
+		assert!(TemplateModule::paused());
+	});
+}
+
+#[test]
+fn council_minority_cannot_use_admin_origins() {
+	new_test_ext_with_council(vec![account(1), account(2), account(3)]).execute_with(|| {
+		// A threshold of one executes right away, with only a third of the council behind it.
+		let call = Call::TemplateModule(template::Call::set_paused(true));
+		propose(account(1), 1, call);
+
+		assert!(!TemplateModule::paused());
+	});
+}
+
+#[test]
+fn council_motion_changes_the_transaction_byte_fee() {
+	new_test_ext_with_council(vec![account(1), account(2), account(3)]).execute_with(|| {
+		let xt = signed_extrinsic(account(1), Call::TemplateModule(template::Call::do_something(42)));
+		let before = Runtime::query_info(xt.clone(), 100).partial_fee;
+
+		let call = Call::Parameters(parameters::Call::set_transaction_byte_fee(11));
+		let (hash, len) = propose(account(1), 2, call);
+		assert_ok!(Council::vote(Origin::signed(account(2)), hash, 0, true));
+		assert_ok!(Council::close(Origin::signed(account(3)), hash, 0, Weight::max_value(), len));
+
+		// `DefaultTransactionByteFee` is 1.
+		assert_eq!(Runtime::query_info(xt, 100).partial_fee - before, 100 * 10);
+	});
+}
+
+fn template_call(call: template::Call<Runtime>) -> Call {
+	Call::TemplateModule(call)
+}
+
+#[test]
+fn batch_all_rolls_back_when_a_call_fails() {
+	let balances = vec![(account(1), 1_000_000)];
+	new_test_ext_with_author(account(0), balances).execute_with(|| {
+		// `cause_error` fails with `NoneValue` before the transfer can take effect.
+		let calls = vec![
+			Call::Balances(BalancesCall::transfer(account(2).into(), 10_000)),
+			template_call(template::Call::cause_error()),
+		];
+		let result = Utility::batch_all(Origin::signed(account(1)), calls);
+
+		assert_eq!(result.map_err(|e| e.error), Err(template::Error::<Runtime>::NoneValue.into()));
+		assert_eq!(Balances::free_balance(&account(1)), 1_000_000);
+		assert_eq!(Balances::free_balance(&account(2)), 0);
+	});
+}
+
+#[test]
+fn batch_all_rolls_back_the_value_and_its_deposit() {
+	let balances = vec![(account(1), 1_000_000)];
+	new_test_ext_with_author(account(0), balances).execute_with(|| {
+		// Stepping past the largest value fails with `StorageOverflow`.
+		let calls = vec![
+			template_call(template::Call::do_something(TemplateValue::max_value())),
+			template_call(template::Call::cause_error()),
+		];
+		let result = Utility::batch_all(Origin::signed(account(1)), calls);
+
+		assert_eq!(
+			result.map_err(|e| e.error),
+			Err(template::Error::<Runtime>::StorageOverflow.into()),
+		);
+		assert_eq!(TemplateModule::something(account(1)), None);
+		assert_eq!(Balances::reserved_balance(&account(1)), 0);
+	});
+}
+
+#[test]
+fn batch_keeps_the_calls_before_a_failure() {
+	let balances = vec![(account(1), 1_000_000)];
+	new_test_ext_with_author(account(0), balances).execute_with(|| {
+		let calls = vec![
+			template_call(template::Call::do_something(TemplateValue::max_value())),
+			template_call(template::Call::cause_error()),
+		];
+		assert_ok!(Utility::batch(Origin::signed(account(1)), calls));
+
+		assert_eq!(TemplateModule::something(account(1)), Some(TemplateValue::max_value()));
+		assert_eq!(Balances::reserved_balance(&account(1)), TemplateValueDeposit::get());
+		assert!(System::events().iter().any(|record| matches!(
+			record.event,
+			Event::utility(utility::Event::BatchInterrupted(1, _))
+		)));
+	});
+}
+
+#[test]
+fn multisig_dispatches_once_the_threshold_approves() {
+	let signatories = vec![account(1), account(2), account(3)];
+	let multisig = Multisig::multi_account_id(&signatories, 2);
+	let balances = vec![
+		(account(1), 1_000_000),
+		(account(2), 1_000_000),
+		(multisig.clone(), 100_000),
+	];
+	new_test_ext_with_author(account(0), balances).execute_with(|| {
+		let call = Box::new(Call::Balances(BalancesCall::transfer(account(4).into(), 10_000)));
+		let weight = call.get_dispatch_info().weight;
+
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(account(1)),
+			2,
+			vec![account(2), account(3)],
+			None,
+			call.clone(),
+			false,
+			0,
+		));
+		assert_eq!(Balances::free_balance(&account(4)), 0);
+		let deposit = MultisigDepositBase::get() + 2 * MultisigDepositFactor::get();
+		assert_eq!(Balances::reserved_balance(&account(1)), deposit);
+
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(account(2)),
+			2,
+			vec![account(1), account(3)],
+			Some(Multisig::timepoint()),
+			call,
+			false,
+			weight,
+		));
+		assert_eq!(Balances::free_balance(&account(4)), 10_000);
+		assert_eq!(Balances::free_balance(&multisig), 90_000);
+		assert_eq!(Balances::reserved_balance(&account(1)), 0);
+	});
+}
+
+/// Builds a genesis where `real` has added `hot` as a proxy of `proxy_type`.
+fn new_test_ext_with_proxy(
+	real: AccountId,
+	hot: AccountId,
+	proxy_type: ProxyType,
+) -> sp_io::TestExternalities {
+	let balances = vec![(real.clone(), 1_000_000), (hot.clone(), 1_000_000)];
+	let mut ext = new_test_ext_with_author(account(0), balances);
+	ext.execute_with(|| assert_ok!(Proxy::add_proxy(Origin::signed(real), hot, proxy_type)));
+	ext
+}
+
+#[test]
+fn template_only_proxy_can_store_values() {
+	new_test_ext_with_proxy(account(1), account(2), ProxyType::TemplateOnly).execute_with(|| {
+		let call = template_call(template::Call::do_something(42));
+		assert_ok!(Proxy::proxy(Origin::signed(account(2)), account(1), None, Box::new(call)));
+
+		assert_eq!(TemplateModule::something(account(1)), Some(42));
+		assert_eq!(TemplateModule::something(account(2)), None);
+	});
+}
+
+#[test]
+fn template_only_proxy_cannot_transfer() {
+	new_test_ext_with_proxy(account(1), account(2), ProxyType::TemplateOnly).execute_with(|| {
+		let free = Balances::free_balance(&account(1));
+		let transfer = Call::Balances(BalancesCall::transfer(account(3).into(), 10_000));
+		assert_ok!(Proxy::proxy(Origin::signed(account(2)), account(1), None, Box::new(transfer.clone())));
+
+		// Nor by wrapping the transfer in a batch.
+		let batch = Call::Utility(utility::Call::batch(vec![transfer]));
+		assert_ok!(Proxy::proxy(Origin::signed(account(2)), account(1), None, Box::new(batch)));
+
+		assert_eq!(Balances::free_balance(&account(1)), free);
+		assert_eq!(Balances::free_balance(&account(3)), 0);
+	});
+}
+
+#[test]
+fn non_transfer_proxy_cannot_act_as_the_sudo_key() {
+	new_test_ext_with_proxy(account(1), account(2), ProxyType::NonTransfer).execute_with(|| {
+		frame_support::storage::migration::put_storage_value(b"Sudo", b"Key", &[], account(1));
+
+		let set_key = Call::Sudo(sudo::Call::set_key(account(2).into()));
+		assert_ok!(Proxy::proxy(Origin::signed(account(2)), account(1), None, Box::new(set_key)));
+
+		assert_eq!(Sudo::key(), account(1));
+	});
+}
+
+#[test]
+fn non_transfer_proxy_cannot_add_an_any_proxy() {
+	new_test_ext_with_proxy(account(1), account(2), ProxyType::NonTransfer).execute_with(|| {
+		let add = Call::Proxy(proxy::Call::add_proxy(account(3), ProxyType::Any));
+		assert_ok!(Proxy::proxy(Origin::signed(account(2)), account(1), None, Box::new(add)));
+		assert_eq!(Proxy::proxies(account(1)).0.len(), 1);
+
+		let add = Call::Proxy(proxy::Call::add_proxy(account(3), ProxyType::TemplateOnly));
+		assert_ok!(Proxy::proxy(Origin::signed(account(2)), account(1), None, Box::new(add)));
+		assert_eq!(Proxy::proxies(account(1)).0.len(), 2);
+	});
+}
+
+#[test]
+fn proxy_types_filter_calls() {
+	let transfer = Call::Balances(BalancesCall::transfer(account(3).into(), 1));
+	let index_transfer = Call::Indices(indices::Call::transfer(account(3), 0));
+	let index_claim = Call::Indices(indices::Call::claim(0));
+	let sudo = Call::Sudo(sudo::Call::sudo(Box::new(transfer.clone())));
+	let store = template_call(template::Call::do_something(1));
+	let pause = template_call(template::Call::set_paused(true));
+	let motion = Call::Council(collective::Call::propose(1, Box::new(pause), 0));
+
+	assert!(ProxyType::Any.filter(&transfer));
+	assert!(!ProxyType::NonTransfer.filter(&transfer));
+	assert!(!ProxyType::NonTransfer.filter(&index_transfer));
+	assert!(ProxyType::NonTransfer.filter(&index_claim));
+	assert!(!ProxyType::NonTransfer.filter(&sudo));
+	assert!(ProxyType::NonTransfer.filter(&store));
+	assert!(ProxyType::TemplateOnly.filter(&store));
+	assert!(!ProxyType::TemplateOnly.filter(&motion));
+	assert!(ProxyType::Governance.filter(&motion));
+	assert!(!ProxyType::Governance.filter(&store));
+
+	assert!(ProxyType::Any.is_superset(&ProxyType::NonTransfer));
+	assert!(ProxyType::NonTransfer.is_superset(&ProxyType::TemplateOnly));
+	assert!(!ProxyType::TemplateOnly.is_superset(&ProxyType::Governance));
+	assert!(!ProxyType::NonTransfer.is_superset(&ProxyType::Any));
+}
+
+#[test]
+fn transfers_can_use_an_account_index() {
+	let balances = vec![(account(1), 1_000_000), (account(2), 1_000_000)];
+	new_test_ext_with_author(account(0), balances).execute_with(|| {
+		assert_ok!(Indices::claim(Origin::signed(account(2)), 0));
+		assert_eq!(Indices::lookup_address(Address::Index(0)), Some(account(2)));
+
+		assert_ok!(Balances::transfer(Origin::signed(account(1)), Address::Index(0), 10_000));
+		assert_eq!(Balances::free_balance(&account(2)), 1_000_000 - IndexDeposit::get() + 10_000);
+
+		// Index 1 has not been claimed.
+		assert!(Balances::transfer(Origin::signed(account(1)), Address::Index(1), 10_000).is_err());
+		assert_eq!(Balances::free_balance(&account(1)), 990_000);
+	});
+}
+
+#[test]
+fn signed_transactions_address_the_account_id() {
+	new_test_ext().execute_with(|| {
+		let address = Indices::unlookup(account(1));
+
+		assert_eq!(address, Address::Id(account(1)));
+		assert_eq!(Indices::lookup(address), Ok(account(1)));
+	});
+}
+
+/// Two prevotes signed by `pair` for different blocks in the same round.
+fn equivocation_proof(pair: &sp_core::ed25519::Pair) -> fg_primitives::EquivocationProof<Hash, BlockNumber> {
+	let set_id = Grandpa::current_set_id();
+	let prevote = |target_hash: Hash| {
+		let prevote = finality_grandpa::Prevote { target_hash, target_number: 1 };
+		let message = finality_grandpa::Message::Prevote(prevote.clone());
+		let payload = fg_primitives::localized_payload(1, set_id, &message);
+		(prevote, pair.sign(&payload).into())
+	};
+	fg_primitives::EquivocationProof::new(
+		set_id,
+		fg_primitives::Equivocation::Prevote(finality_grandpa::Equivocation {
+			round_number: 1,
+			identity: pair.public().into(),
+			first: prevote(Hash::repeat_byte(1)),
+			second: prevote(Hash::repeat_byte(2)),
+		}),
+	)
+}
+
+#[test]
+fn key_ownership_proofs_exist_for_session_validators() {
+	new_test_ext_with_author(account(1), vec![]).execute_with(|| {
+		let set_id = Grandpa::current_set_id();
+
+		let validator = GrandpaId::from(grandpa_pair(1).public());
+		assert!(Runtime::generate_key_ownership_proof(set_id, validator).is_some());
+
+		let stranger = GrandpaId::from(grandpa_pair(2).public());
+		assert!(Runtime::generate_key_ownership_proof(set_id, stranger).is_none());
+	});
+}
+
+#[test]
+fn equivocation_reports_are_recorded_as_offences() {
+	new_test_ext_with_author(account(1), vec![]).execute_with(|| {
+		let pair = grandpa_pair(1);
+		let key_owner_proof = Historical::prove((fg_primitives::KEY_TYPE, GrandpaId::from(pair.public())))
+			.unwrap();
+
+		assert_ok!(Grandpa::report_equivocation_unsigned(
+			Origin::none(),
+			equivocation_proof(&pair),
+			key_owner_proof,
+		));
+
+		assert!(System::events().iter().any(|record| matches!(
+			record.event,
+			Event::offences(offences::Event::Offence(..))
+		)));
+	});
+}
+
+#[test]
+fn council_motion_spends_from_the_treasury() {
+	new_test_ext_with_council(vec![account(1), account(2), account(3)]).execute_with(|| {
+		let _ = Balances::deposit_creating(&Treasury::account_id(), 100_000);
+
+		let call = Call::Treasury(treasury::Call::spend(account(4), 10_000));
+		let (hash, len) = propose(account(1), 2, call);
+		assert_ok!(Council::vote(Origin::signed(account(2)), hash, 0, true));
+		assert_ok!(Council::close(Origin::signed(account(3)), hash, 0, Weight::max_value(), len));
+
+		assert_eq!(Balances::free_balance(&account(4)), 10_000);
+		assert_eq!(Balances::free_balance(&Treasury::account_id()), 90_000);
+	});
+}
+
+#[test]
+fn sudo_seats_the_first_council() {
+	let mut t = system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+	sudo::GenesisConfig::<Runtime> { key: account(1) }.assimilate_storage(&mut t).unwrap();
+	sp_io::TestExternalities::from(t).execute_with(|| {
+		let members = vec![account(2), account(3), account(4)];
+		let call = Call::Council(collective::Call::set_members(members.clone(), None));
+		assert_ok!(Sudo::sudo(Origin::signed(account(1)), Box::new(call)));
+
+		assert_eq!(Council::members(), members);
+	});
+}
+
+/// Moves to block `n`, running the hooks of `Democracy` and `Scheduler` on the way.
+fn run_governance_to_block(n: BlockNumber) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Democracy::on_initialize(System::block_number());
+		Scheduler::on_initialize(System::block_number());
+	}
+}
+
+#[test]
+fn council_external_proposal_is_enacted_through_a_referendum() {
+	new_test_ext_with_council(vec![account(1), account(2), account(3)]).execute_with(|| {
+		let _ = Balances::deposit_creating(&account(1), 1_000_000);
+		let proposal = Call::TemplateModule(template::Call::set_paused(true));
+		assert_ok!(Democracy::note_preimage(Origin::signed(account(1)), proposal.encode()));
+
+		let call = Call::Democracy(democracy::Call::external_propose(BlakeTwo256::hash_of(&proposal)));
+		let (hash, len) = propose(account(1), 2, call);
+		assert_ok!(Council::vote(Origin::signed(account(2)), hash, 0, true));
+		assert_ok!(Council::close(Origin::signed(account(3)), hash, 0, Weight::max_value(), len));
+
+		// The external proposal becomes a referendum at the end of the launch period.
+		let launched = LaunchPeriod::get();
+		run_governance_to_block(launched);
+		let aye = democracy::AccountVote::Standard {
+			vote: democracy::Vote { aye: true, conviction: democracy::Conviction::Locked1x },
+			balance: 100_000,
+		};
+		assert_ok!(Democracy::vote(Origin::signed(account(1)), 0, aye));
+
+		// It passes at the end of the voting period, and is enacted after the enactment period.
+		let enacted = launched + VotingPeriod::get() + EnactmentPeriod::get();
+		run_governance_to_block(enacted - 1);
+		assert!(!TemplateModule::paused());
+
+		run_governance_to_block(enacted);
+		assert!(TemplateModule::paused());
+	});
+}
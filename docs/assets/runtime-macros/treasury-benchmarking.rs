@@ -0,0 +1,39 @@
+//! Benchmarking setup for treasury
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{benchmarks, account};
+use sp_runtime::traits::Bounded;
+
+const SEED: u32 = 0;
+
+benchmarks! {
+	_ { }
+
+	spend {
+		T::Currency::make_free_balance_be(&Module::<T>::account_id(), BalanceOf::<T>::max_value());
+		// Paying a new account is the most expensive transfer.
+		let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+		let amount = T::Currency::minimum_balance().saturating_mul(100u32.into());
+		let origin = T::SpendOrigin::successful_origin();
+	}: { Module::<T>::spend(origin, beneficiary.clone(), amount)?; }
+	verify {
+		assert_eq!(T::Currency::free_balance(&beneficiary), amount);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_spend::<Test>());
+		});
+	}
+}
@@ -0,0 +1,59 @@
+// Tests to be written here
+
+use crate::{RawEvent, mock::*};
+use frame_support::{assert_ok, assert_noop, traits::{Currency, OnUnbalanced}};
+use sp_runtime::DispatchError;
+
+fn last_event() -> TestEvent {
+	System::events().pop().expect("An event was expected").event
+}
+
+#[test]
+fn genesis_creates_the_pot() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Balances::free_balance(Treasury::account_id()), 10);
+		assert_eq!(Treasury::pot(), 0);
+	});
+}
+
+#[test]
+fn imbalances_are_paid_into_the_pot() {
+	new_test_ext().execute_with(|| {
+		let issuance = Balances::total_issuance();
+
+		// Below the existential deposit, which a new account would burn.
+		let (fee, _) = Balances::slash(&1, 5);
+		Treasury::on_unbalanced(fee);
+
+		assert_eq!(Treasury::pot(), 5);
+		assert_eq!(Balances::total_issuance(), issuance);
+		assert_eq!(last_event(), TestEvent::treasury(RawEvent::Deposit(5)));
+	});
+}
+
+#[test]
+fn spend_origin_spends_from_the_pot() {
+	new_test_ext().execute_with(|| {
+		let (fee, _) = Balances::slash(&1, 500);
+		Treasury::on_unbalanced(fee);
+
+		assert_noop!(Treasury::spend(Origin::signed(1), 2, 100), DispatchError::BadOrigin);
+
+		assert_ok!(Treasury::spend(Origin::root(), 2, 100));
+		assert_eq!(Balances::free_balance(2), 100);
+		assert_eq!(Treasury::pot(), 400);
+		assert_eq!(last_event(), TestEvent::treasury(RawEvent::Spent(2, 100)));
+	});
+}
+
+#[test]
+fn spend_keeps_the_pot_alive() {
+	new_test_ext().execute_with(|| {
+		let (fee, _) = Balances::slash(&1, 500);
+		Treasury::on_unbalanced(fee);
+
+		assert!(Treasury::spend(Origin::root(), 2, 505).is_err());
+		assert_ok!(Treasury::spend(Origin::root(), 2, 500));
+		assert_eq!(Balances::free_balance(Treasury::account_id()), 10);
+	});
+}
@@ -0,0 +1,36 @@
+//! Weights for treasury
+//!
+//! These are hand-written placeholders, not benchmark results: each call is charged a round
+//! base weight plus the storage reads and writes it makes. Replace this file with the output
+//! of `./target/release/node-template benchmark --pallet=treasury --extrinsic=*` on reference
+//! hardware before relying on them.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for treasury.
+pub trait WeightInfo {
+	fn spend() -> Weight;
+}
+
+/// Weights for treasury using the database weights of the runtime.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+	fn spend() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn spend() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+}
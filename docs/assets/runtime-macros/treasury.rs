@@ -0,0 +1,121 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// A FRAME pallet which keeps a pot of funds for the chain, paid from fees, dust and slashes.
+///
+/// Funds reach the pot through its `OnUnbalanced` implementation, and only leave it through
+/// `spend`, which is dispatched by `SpendOrigin`. The pot is kept at or above the existential
+/// deposit so it is never reaped.
+
+use frame_support::{
+	decl_module, decl_storage, decl_event, dispatch,
+	traits::{Currency, Get, EnsureOrigin, ExistenceRequirement::KeepAlive, Imbalance, OnUnbalanced},
+};
+use frame_system::{self as system};
+use sp_runtime::{ModuleId, traits::{AccountIdConversion, Saturating}};
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+type BalanceOf<T> =
+	<<T as Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
+type NegativeImbalanceOf<T> =
+	<<T as Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::NegativeImbalance;
+
+/// The pallet's configuration trait.
+pub trait Trait: system::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+
+	/// The currency held in the pot.
+	type Currency: Currency<Self::AccountId>;
+
+	/// The id from which the pot's account is derived.
+	type ModuleId: Get<ModuleId>;
+
+	/// The origin which may spend from the pot.
+	type SpendOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Weight information for the extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Treasury {}
+	add_extra_genesis {
+		build(|_config| {
+			// Create the pot's account, so that amounts below the existential deposit paid
+			// into it are kept rather than burned.
+			let _ = T::Currency::make_free_balance_be(
+				&<Module<T>>::account_id(),
+				T::Currency::minimum_balance(),
+			);
+		});
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where
+		AccountId = <T as system::Trait>::AccountId,
+		Balance = BalanceOf<T>,
+	{
+		/// Funds were paid into the pot.
+		Deposit(Balance),
+		/// Funds were paid from the pot to an account.
+		Spent(AccountId, Balance),
+	}
+);
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		fn deposit_event() = default;
+
+		/// The id from which the pot's account is derived.
+		const ModuleId: ModuleId = T::ModuleId::get();
+
+		/// Pays `amount` from the pot to `beneficiary`.
+		///
+		/// Fails if this would take the pot below the existential deposit.
+		///
+		/// The dispatch origin for this call must be `SpendOrigin`.
+		#[weight = T::WeightInfo::spend()]
+		pub fn spend(origin, beneficiary: T::AccountId, amount: BalanceOf<T>) -> dispatch::DispatchResult {
+			T::SpendOrigin::ensure_origin(origin)?;
+
+			T::Currency::transfer(&Self::account_id(), &beneficiary, amount, KeepAlive)?;
+
+			Self::deposit_event(RawEvent::Spent(beneficiary, amount));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// The account holding the pot.
+	pub fn account_id() -> T::AccountId {
+		T::ModuleId::get().into_account()
+	}
+
+	/// The amount which can be spent, that is everything above the existential deposit.
+	pub fn pot() -> BalanceOf<T> {
+		T::Currency::free_balance(&Self::account_id())
+			.saturating_sub(T::Currency::minimum_balance())
+	}
+}
+
+impl<T: Trait> OnUnbalanced<NegativeImbalanceOf<T>> for Module<T> {
+	fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<T>) {
+		let value = amount.peek();
+		T::Currency::resolve_creating(&Self::account_id(), amount);
+
+		Self::deposit_event(RawEvent::Deposit(value));
+	}
+}
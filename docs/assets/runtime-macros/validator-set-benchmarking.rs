@@ -0,0 +1,57 @@
+//! Benchmarking setup for validator-set
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{benchmarks, account};
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+// Fills the set with `count` validators. Checking for an existing validator is linear in
+// the size of the set, so the benchmarks run with it as full as each call allows.
+fn fill_validators<T: Trait>(count: u32) -> Vec<T::AccountId> {
+	let validators: Vec<T::AccountId> = (0..count)
+		.map(|i| account("validator", i, SEED))
+		.collect();
+	Validators::<T>::put(&validators);
+	validators
+}
+
+benchmarks! {
+	_ { }
+
+	add_validator {
+		fill_validators::<T>(T::MaxValidators::get() - 1);
+		let who: T::AccountId = account("who", 0, SEED);
+		let origin = T::AddRemoveOrigin::successful_origin();
+	}: { Module::<T>::add_validator(origin, who.clone())?; }
+	verify {
+		assert!(Validators::<T>::get().contains(&who));
+	}
+
+	remove_validator {
+		// The last validator is the one found last.
+		let who = fill_validators::<T>(T::MaxValidators::get()).pop().unwrap();
+		let origin = T::AddRemoveOrigin::successful_origin();
+	}: { Module::<T>::remove_validator(origin, who.clone())?; }
+	verify {
+		assert!(!Validators::<T>::get().contains(&who));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_add_validator::<Test>());
+			assert_ok!(test_benchmark_remove_validator::<Test>());
+		});
+	}
+}
@@ -0,0 +1,109 @@
+// Creating mock runtime here
+
+use crate::{Module, Trait};
+use sp_core::{H256, crypto::key_types::DUMMY};
+use frame_support::{impl_outer_origin, impl_outer_event, parameter_types, weights::Weight};
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup, OpaqueKeys}, testing::{Header, UintAuthorityId},
+	KeyTypeId, Perbill,
+};
+use frame_system as system;
+use crate as validator_set;
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Test {
+		system<T>,
+		session,
+		validator_set<T>,
+	}
+}
+
+// Ignores the session keys: the tests only check which validators are active.
+pub struct TestSessionHandler;
+impl session::SessionHandler<u64> for TestSessionHandler {
+	const KEY_TYPE_IDS: &'static [KeyTypeId] = &[DUMMY];
+	fn on_genesis_session<Ks: OpaqueKeys>(_validators: &[(u64, Ks)]) {}
+	fn on_new_session<Ks: OpaqueKeys>(
+		_changed: bool,
+		_validators: &[(u64, Ks)],
+		_queued_validators: &[(u64, Ks)],
+	) {}
+	fn on_disabled(_validator_index: usize) {}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+	pub const Period: u64 = 5;
+	pub const Offset: u64 = 0;
+	pub const DisabledValidatorsThreshold: Perbill = Perbill::from_percent(33);
+	pub const MaxValidators: u32 = 4;
+}
+impl system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Call = ();
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = TestEvent;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type ModuleToIndex = ();
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+}
+impl session::Trait for Test {
+	type Event = TestEvent;
+	type ValidatorId = u64;
+	type ValidatorIdOf = crate::ValidatorOf<Test>;
+	type ShouldEndSession = session::PeriodicSessions<Period, Offset>;
+	type NextSessionRotation = session::PeriodicSessions<Period, Offset>;
+	type SessionManager = ValidatorSet;
+	type SessionHandler = TestSessionHandler;
+	type Keys = UintAuthorityId;
+	type DisabledValidatorsThreshold = DisabledValidatorsThreshold;
+	type WeightInfo = ();
+}
+impl Trait for Test {
+	type Event = TestEvent;
+	type AddRemoveOrigin = system::EnsureRoot<u64>;
+	type MaxValidators = MaxValidators;
+	type WeightInfo = ();
+}
+pub type System = system::Module<Test>;
+pub type Session = session::Module<Test>;
+pub type ValidatorSet = Module<Test>;
+
+// Builds a genesis with validators 1, 2 and 3, each with session keys registered.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	// The validator set must be in storage before the session genesis asks for it.
+	validator_set::GenesisConfig::<Test> {
+		validators: vec![1, 2, 3],
+	}.assimilate_storage(&mut t).unwrap();
+	session::GenesisConfig::<Test> {
+		keys: vec![1, 2, 3].into_iter().map(|i| (i, i, UintAuthorityId(i))).collect(),
+	}.assimilate_storage(&mut t).unwrap();
+	t.into()
+}
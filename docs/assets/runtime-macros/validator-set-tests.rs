@@ -0,0 +1,91 @@
+use crate::{Error, RawEvent, mock::*};
+use frame_support::{assert_ok, assert_noop, traits::OnInitialize};
+use session::SessionManager;
+use sp_runtime::{DispatchError, testing::UintAuthorityId};
+
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Session::on_initialize(System::block_number());
+	}
+}
+
+#[test]
+fn genesis_validators_are_active() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(ValidatorSet::validators(), vec![1, 2, 3]);
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+	});
+}
+
+#[test]
+fn added_validator_takes_effect_after_a_session() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(Session::set_keys(Origin::signed(4), UintAuthorityId(4), vec![]));
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		assert!(System::events().iter().any(|record|
+			record.event == TestEvent::validator_set(RawEvent::ValidatorAdded(4))
+		));
+
+		// `Period` is 5 in the mock. The first rotation queues the new validator...
+		run_to_block(5);
+		assert_eq!(Session::current_index(), 1);
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+		assert!(Session::queued_keys().iter().any(|(who, _)| *who == 4));
+
+		// ...and the session after that makes it active.
+		run_to_block(10);
+		assert_eq!(Session::current_index(), 2);
+		assert_eq!(Session::validators(), vec![1, 2, 3, 4]);
+	});
+}
+
+#[test]
+fn removed_validator_leaves_after_a_session() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 3));
+		assert_eq!(ValidatorSet::validators(), vec![1, 2]);
+
+		run_to_block(10);
+		assert_eq!(Session::validators(), vec![1, 2]);
+	});
+}
+
+#[test]
+fn validator_changes_are_checked() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(ValidatorSet::add_validator(Origin::signed(1), 4), DispatchError::BadOrigin);
+		assert_noop!(ValidatorSet::remove_validator(Origin::signed(1), 3), DispatchError::BadOrigin);
+
+		assert_noop!(ValidatorSet::add_validator(Origin::root(), 1), Error::<Test>::AlreadyValidator);
+		assert_noop!(ValidatorSet::remove_validator(Origin::root(), 4), Error::<Test>::NotValidator);
+
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 1));
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 2));
+		assert_noop!(ValidatorSet::remove_validator(Origin::root(), 3), Error::<Test>::TooFewValidators);
+	});
+}
+
+#[test]
+fn validators_are_only_handed_over_when_changed() {
+	new_test_ext().execute_with(|| {
+		// The session genesis took the initial validators.
+		assert!(!ValidatorSet::changed());
+		assert_eq!(<ValidatorSet as SessionManager<u64>>::new_session(1), None);
+
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 3));
+		assert!(ValidatorSet::changed());
+		assert_eq!(<ValidatorSet as SessionManager<u64>>::new_session(2), Some(vec![1, 2]));
+		assert_eq!(<ValidatorSet as SessionManager<u64>>::new_session(3), None);
+	});
+}
+
+#[test]
+fn validator_set_is_bounded() {
+	new_test_ext().execute_with(|| {
+		// `MaxValidators` is 4 in the mock.
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		assert_noop!(ValidatorSet::add_validator(Origin::root(), 5), Error::<Test>::TooManyValidators);
+	});
+}
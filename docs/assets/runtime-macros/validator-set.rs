@@ -0,0 +1,179 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// A FRAME pallet which keeps the set of validators used by `pallet_session`.
+///
+/// Validators are added and removed by `AddRemoveOrigin`. Changes are handed to the session
+/// pallet at the next session boundary, which in turn rotates the Aura and GRANDPA authorities.
+
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, dispatch, ensure,
+	traits::{EnsureOrigin, Get},
+};
+use frame_system::{self as system};
+use sp_runtime::traits::Convert;
+use sp_staking::SessionIndex;
+use sp_std::prelude::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+/// The pallet's configuration trait.
+pub trait Trait: system::Trait + session::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+
+	/// The origin which may add or remove validators.
+	type AddRemoveOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The largest number of validators in the set. Adding and removing a validator scans
+	/// the whole set, so their weights are measured with this many.
+	type MaxValidators: Get<u32>;
+
+	/// Weight information for the extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as ValidatorSet {
+		// The validators handed to the session pallet at the next session boundary.
+		Validators get(fn validators) config() build(|config: &GenesisConfig<T>| {
+			assert!(
+				config.validators.len() <= T::MaxValidators::get() as usize,
+				"More than `MaxValidators` validators at genesis",
+			);
+			config.validators.clone()
+		}): Vec<T::AccountId>;
+
+		// Whether `Validators` changed since it was last handed to the session pallet.
+		// Set at genesis, so the session genesis takes the initial validators from here.
+		Changed get(fn changed) build(|_| true): bool;
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where AccountId = <T as system::Trait>::AccountId {
+		/// A validator was added. It becomes active once the session pallet rotates it in.
+		ValidatorAdded(AccountId),
+		/// A validator was removed. It stays active until the session pallet rotates it out.
+		ValidatorRemoved(AccountId),
+	}
+);
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The account is already a validator
+		AlreadyValidator,
+		/// The account is not a validator
+		NotValidator,
+		/// The last validator cannot be removed
+		TooFewValidators,
+		/// The validator set already holds `MaxValidators` validators
+		TooManyValidators,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// The largest number of validators in the set.
+		const MaxValidators: u32 = T::MaxValidators::get();
+
+		/// Adds `who` to the validator set from the next session boundary.
+		///
+		/// `who` must have registered session keys with `Session::set_keys`, otherwise the
+		/// session pallet skips it.
+		///
+		/// The dispatch origin for this call must be `AddRemoveOrigin`.
+		#[weight = T::WeightInfo::add_validator()]
+		pub fn add_validator(origin, who: T::AccountId) -> dispatch::DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+
+			<Validators<T>>::try_mutate(|validators| {
+				ensure!(!validators.contains(&who), Error::<T>::AlreadyValidator);
+				ensure!(
+					validators.len() < T::MaxValidators::get() as usize,
+					Error::<T>::TooManyValidators
+				);
+				validators.push(who.clone());
+				Ok::<_, Error<T>>(())
+			})?;
+			Changed::put(true);
+
+			Self::deposit_event(RawEvent::ValidatorAdded(who));
+			Ok(())
+		}
+
+		/// Removes `who` from the validator set from the next session boundary.
+		///
+		/// The dispatch origin for this call must be `AddRemoveOrigin`.
+		#[weight = T::WeightInfo::remove_validator()]
+		pub fn remove_validator(origin, who: T::AccountId) -> dispatch::DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+
+			<Validators<T>>::try_mutate(|validators| {
+				let index = validators.iter().position(|v| v == &who)
+					.ok_or(Error::<T>::NotValidator)?;
+				ensure!(validators.len() > 1, Error::<T>::TooFewValidators);
+				validators.remove(index);
+				Ok::<_, Error<T>>(())
+			})?;
+			Changed::put(true);
+
+			Self::deposit_event(RawEvent::ValidatorRemoved(who));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Trait> session::SessionManager<T::AccountId> for Module<T> {
+	/// Hands over the validators only when they changed, so the session pallet does not
+	/// schedule an identical authority set.
+	fn new_session(_new_index: SessionIndex) -> Option<Vec<T::AccountId>> {
+		if Changed::take() {
+			Some(Self::validators())
+		} else {
+			None
+		}
+	}
+
+	fn end_session(_end_index: SessionIndex) {}
+
+	fn start_session(_start_index: SessionIndex) {}
+}
+
+/// Validators have no stake to expose, so their full identification is their account.
+impl<T: Trait> session::historical::SessionManager<T::AccountId, T::AccountId> for Module<T> {
+	fn new_session(new_index: SessionIndex) -> Option<Vec<(T::AccountId, T::AccountId)>> {
+		<Self as session::SessionManager<_>>::new_session(new_index)
+			.map(|validators| validators.into_iter().map(|v| (v.clone(), v)).collect())
+	}
+
+	fn end_session(end_index: SessionIndex) {
+		<Self as session::SessionManager<_>>::end_session(end_index)
+	}
+
+	fn start_session(start_index: SessionIndex) {
+		<Self as session::SessionManager<_>>::start_session(start_index)
+	}
+}
+
+/// Converts an account into its validator id. Validators are identified by their account.
+pub struct ValidatorOf<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Trait> Convert<T::AccountId, Option<T::AccountId>> for ValidatorOf<T> {
+	fn convert(account: T::AccountId) -> Option<T::AccountId> {
+		Some(account)
+	}
+}